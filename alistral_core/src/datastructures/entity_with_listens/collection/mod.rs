@@ -119,6 +119,27 @@ where
 
         None
     }
+
+    /// Return the rank of the listened entity, scaled over the whole collection. See [`rank_ratio`]
+    pub fn get_rank_ratio(&self, entity: &Ent) -> Decimal {
+        rank_ratio(self.get_rank(entity), self.0.len())
+    }
+}
+
+/// Scale the rank of an entity over a collection of `count` entities.
+///
+/// The most listened entity gets 1, and the least listened gets 0. Entities outside of the collection (`None`) get 0 too
+pub fn rank_ratio(rank: Option<usize>, count: usize) -> Decimal {
+    let Some(rank) = rank else {
+        return Decimal::ZERO;
+    };
+
+    let last_rank = count.saturating_sub(1);
+    if last_rank == 0 {
+        return Decimal::ONE;
+    }
+
+    Decimal::ONE - Decimal::from(rank) / Decimal::from(last_rank)
 }
 
 impl<Ent, Lis> Default for EntityWithListensCollection<Ent, Lis>
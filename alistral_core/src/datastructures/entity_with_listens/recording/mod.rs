@@ -33,13 +33,10 @@ impl RecordingWithListens {
     }
 
     fn get_underrated_rank_score(&self, user_listens: &RecordingWithListensCollection) -> Decimal {
-        // Retrive the rank of the recording in the user listens
-        let rank = user_listens.get_rank(&self.entity).unwrap_or(9999);
-
-        // The recording vec scores if its between 0 and 999.
-        Decimal::from(1000_u64.saturating_sub(rank.try_into().unwrap()))
-            .checked_div(dec!(10))
-            .unwrap_or(Decimal::ZERO)
+        // x100 to get the score to max out at 100 points
+        user_listens
+            .get_rank_ratio(&self.entity)
+            .saturating_mul(dec!(100))
     }
 
     fn get_underrated_listen_score(
@@ -1,8 +1,63 @@
 use musicbrainz_db_lite::models::musicbrainz::release_group::ReleaseGroup;
+use musicbrainz_db_lite::RowId;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 
 use crate::datastructures::entity_with_listens::EntityWithListens;
+use crate::datastructures::listen_collection::traits::ListenCollectionReadable;
 use crate::datastructures::listen_collection::ListenCollection;
 
+use self::collection::ReleaseGroupWithListensCollection;
+
 pub mod collection;
 
 pub type ReleaseGroupWithListens = EntityWithListens<ReleaseGroup, ListenCollection>;
+
+impl ReleaseGroupWithListens {
+    pub fn release_group(&self) -> &ReleaseGroup {
+        &self.entity
+    }
+
+    /// Get the score for the underated album radio
+    pub fn get_underated_score(
+        &self,
+        user_listens: &ReleaseGroupWithListensCollection,
+        global_listen_count: u64,
+    ) -> Decimal {
+        self.get_underrated_rank_score(user_listens)
+            + self.get_underrated_listen_score(user_listens, global_listen_count)
+    }
+
+    fn get_underrated_rank_score(
+        &self,
+        user_listens: &ReleaseGroupWithListensCollection,
+    ) -> Decimal {
+        // x100 to get the score to max out at 100 points
+        user_listens
+            .get_rank_ratio(&self.entity)
+            .saturating_mul(dec!(100))
+    }
+
+    fn get_underrated_listen_score(
+        &self,
+        user_listens: &ReleaseGroupWithListensCollection,
+        global_listen_count: u64,
+    ) -> Decimal {
+        // Retrieve the all time listen count of the release group for the user
+        let all_time_listen_count = user_listens
+            .get_by_id(self.entity.get_row_id())
+            .map(|r| r.listen_count())
+            .unwrap_or(0);
+
+        // Divide by the global listen count to get the fractions of listens made by the user
+        // Then x100 to get the score to max out at 100 points
+        Decimal::from(all_time_listen_count)
+            .checked_div(Decimal::from(global_listen_count))
+            // If `global_listen_count` is 0, it means that the stats haven't been processed yet on LB's side.
+            // We'll be optimist and assume the user is the only listener
+            .unwrap_or(dec!(1))
+            // if `global_listen_count` is inferior to the stats, we cap at one.
+            .min(Decimal::ONE)
+            .saturating_mul(dec!(100))
+    }
+}
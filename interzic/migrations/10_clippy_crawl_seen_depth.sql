@@ -0,0 +1,2 @@
+-- Remember the depth the checked entities got expanded from, so they get expanded again when reached from closer
+ALTER TABLE `clippy_crawl_seen` ADD COLUMN `depth` INTEGER NOT NULL DEFAULT 0;
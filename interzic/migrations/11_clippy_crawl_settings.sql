@@ -0,0 +1,2 @@
+-- Keep the settings of the crawl, so `--resume` continues it the same way
+CREATE TABLE `clippy_crawl_settings` (`id` INTEGER NOT NULL, `new_first` INTEGER NOT NULL, `max_depth` INTEGER, `max_count` INTEGER, PRIMARY KEY (`id`)) STRICT;
@@ -0,0 +1,2 @@
+-- Keep the MusicBrainz API responses that the local database doesn't cover, so they aren't fetched on each check and can be used offline
+CREATE TABLE `musicbrainz_api_cache` (`entity_type` TEXT NOT NULL, `mbid` TEXT NOT NULL, `data` TEXT NOT NULL, `fetched_at` INTEGER NOT NULL, PRIMARY KEY (`entity_type`, `mbid`)) STRICT;
@@ -0,0 +1,2 @@
+-- Add migration script here
+ALTER TABLE `recording` ADD COLUMN `duration` INTEGER;
@@ -0,0 +1,3 @@
+-- Add migration script here
+CREATE TABLE `local_file` (`id` INTEGER PRIMARY KEY AUTOINCREMENT UNIQUE NOT NULL, `path` TEXT UNIQUE NOT NULL, `recording_mbid` TEXT NOT NULL, `modified_at` INTEGER NOT NULL) STRICT;
+CREATE INDEX `idx_local_file_recording` ON `local_file` (`recording_mbid`);
@@ -0,0 +1,4 @@
+-- Add migration script here
+CREATE TABLE `playlist_history` (`id` INTEGER PRIMARY KEY AUTOINCREMENT UNIQUE NOT NULL, `title` TEXT NOT NULL, `description` TEXT NOT NULL, `kind` TEXT NOT NULL, `parameters` TEXT NOT NULL, `target` TEXT NOT NULL, `created_at` INTEGER NOT NULL) STRICT;
+CREATE TABLE `playlist_history_recording` (`playlist_id` INTEGER NOT NULL REFERENCES `playlist_history`(`id`) ON DELETE CASCADE, `position` INTEGER NOT NULL, `recording_id` INTEGER NOT NULL REFERENCES `recording`(`id`), PRIMARY KEY (`playlist_id`, `position`)) STRICT;
+CREATE INDEX `idx_playlist_history_created_at` ON `playlist_history` (`created_at`);
@@ -0,0 +1,2 @@
+-- Add migration script here
+CREATE TABLE `recording_feedback` (`username` TEXT NOT NULL, `recording_mbid` TEXT NOT NULL, `score` INTEGER NOT NULL, `created_at` INTEGER NOT NULL, PRIMARY KEY (`username`, `recording_mbid`)) STRICT;
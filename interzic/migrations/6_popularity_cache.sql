@@ -0,0 +1,2 @@
+-- Add migration script here
+CREATE TABLE `popularity_cache` (`entity_type` TEXT NOT NULL, `mbid` TEXT NOT NULL, `total_listen_count` INTEGER, `total_user_count` INTEGER, `fetched_at` INTEGER NOT NULL, PRIMARY KEY (`entity_type`, `mbid`)) STRICT;
@@ -0,0 +1,3 @@
+-- Add migration script here
+CREATE TABLE `clippy_crawl_queue` (`position` INTEGER NOT NULL, `entity_type` TEXT NOT NULL, `mbid` TEXT NOT NULL, `depth` INTEGER NOT NULL, PRIMARY KEY (`position`)) STRICT;
+CREATE TABLE `clippy_crawl_seen` (`mbid` TEXT NOT NULL, PRIMARY KEY (`mbid`)) STRICT;
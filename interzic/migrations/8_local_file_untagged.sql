@@ -0,0 +1,6 @@
+-- Keep the files without recording MBID, so their tags aren't read again until they change
+CREATE TABLE `local_file_new` (`id` INTEGER PRIMARY KEY AUTOINCREMENT UNIQUE NOT NULL, `path` TEXT UNIQUE NOT NULL, `recording_mbid` TEXT, `modified_at` INTEGER NOT NULL) STRICT;
+INSERT INTO `local_file_new` SELECT * FROM `local_file`;
+DROP TABLE `local_file`;
+ALTER TABLE `local_file_new` RENAME TO `local_file`;
+CREATE INDEX `idx_local_file_recording` ON `local_file` (`recording_mbid`);
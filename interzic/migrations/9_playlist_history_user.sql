@@ -0,0 +1,3 @@
+-- Remember who the playlists got generated for, so the history of a user doesn't filter the radios of another
+ALTER TABLE `playlist_history` ADD COLUMN `user` TEXT;
+CREATE INDEX `idx_playlist_history_user` ON `playlist_history` (`user`, `created_at`);
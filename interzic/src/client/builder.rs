@@ -10,6 +10,8 @@ use sqlx::sqlite::SqlitePoolOptions;
 
 use crate::InterzicClient;
 
+const DEFAULT_LISTENBRAINZ_URL: &str = "https://api.listenbrainz.org/1/";
+
 #[derive(Default)]
 pub struct ClientBuilder {
     pub database_client: Option<sqlx::SqlitePool>,
@@ -62,6 +64,8 @@ impl ClientBuilder {
             musicbrainz_client: None,
             youtube_client: None,
             listenbrainz_client: None,
+            listenbrainz_url: DEFAULT_LISTENBRAINZ_URL.to_string(),
+            http_client: reqwest::Client::new(),
             musicbrainz_db_lite_client: None,
         })
     }
@@ -20,6 +20,13 @@ pub struct InterzicClient {
     musicbrainz_client: Option<Arc<MusicBrainzClient>>,
     musicbrainz_db_lite_client: Option<Arc<DBClient>>,
     listenbrainz_client: Option<Arc<listenbrainz::raw::Client>>,
+
+    /// The root URL of the ListenBrainz API, for the requests the listenbrainz client doesn't support
+    listenbrainz_url: String,
+
+    /// The HTTP client shared by the requests that the service clients don't support
+    http_client: reqwest::Client,
+
     youtube_client: Option<Arc<YoutubeClient>>,
 }
 
@@ -74,6 +81,19 @@ impl InterzicClient {
         self.listenbrainz_client = Some(client);
     }
 
+    pub fn set_listenbrainz_url(&mut self, url: String) {
+        self.listenbrainz_url = url;
+    }
+
+    pub fn http_client(&self) -> &reqwest::Client {
+        &self.http_client
+    }
+
+    /// Get the full URL of a ListenBrainz API endpoint
+    pub fn listenbrainz_endpoint(&self, endpoint: &str) -> String {
+        format!("{}/{endpoint}", self.listenbrainz_url.trim_end_matches('/'))
+    }
+
     pub fn listenbrainz_client(&self) -> Result<&listenbrainz::raw::Client, crate::Error> {
         self.listenbrainz_client
             .as_ref()
@@ -0,0 +1,133 @@
+use sqlx::Acquire;
+use sqlx::Sqlite;
+
+use crate::models::clippy_crawl::ClippyCrawlChanges;
+use crate::models::clippy_crawl::ClippyCrawlNode;
+use crate::models::clippy_crawl::ClippyCrawlSettings;
+use crate::models::clippy_crawl::ClippyCrawlState;
+
+impl ClippyCrawlState {
+    /// Replace the saved crawl with this one
+    pub async fn save<'a, A>(&self, conn: A) -> Result<(), sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+        let mut trans = Acquire::begin(&mut *conn).await?;
+
+        sqlx::query("DELETE FROM clippy_crawl_queue;")
+            .execute(&mut *trans)
+            .await?;
+        sqlx::query("DELETE FROM clippy_crawl_seen;")
+            .execute(&mut *trans)
+            .await?;
+        sqlx::query("DELETE FROM clippy_crawl_settings;")
+            .execute(&mut *trans)
+            .await?;
+
+        sqlx::query("INSERT INTO clippy_crawl_settings VALUES (0, ?, ?, ?);")
+            .bind(self.settings.new_first)
+            .bind(self.settings.max_depth)
+            .bind(self.settings.max_count)
+            .execute(&mut *trans)
+            .await?;
+
+        let changes = ClippyCrawlChanges {
+            added: self.queue.clone(),
+            removed: Vec::new(),
+            seen: self.seen.clone(),
+        };
+        changes.apply(&mut *trans).await?;
+
+        trans.commit().await
+    }
+
+    /// Load the saved crawl. Returns `None` if there's no crawl to resume
+    pub async fn load<'a, A>(conn: A) -> Result<Option<Self>, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        let queue: Vec<ClippyCrawlNode> = sqlx::query_as(
+            "SELECT position, entity_type, mbid, depth FROM clippy_crawl_queue ORDER BY position;",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        if queue.is_empty() {
+            return Ok(None);
+        }
+
+        let seen: Vec<(String, i64)> = sqlx::query_as("SELECT mbid, depth FROM clippy_crawl_seen;")
+            .fetch_all(&mut *conn)
+            .await?;
+
+        let settings: ClippyCrawlSettings = sqlx::query_as(
+            "SELECT new_first, max_depth, max_count FROM clippy_crawl_settings WHERE id = 0;",
+        )
+        .fetch_optional(&mut *conn)
+        .await?
+        .unwrap_or_default();
+
+        Ok(Some(Self {
+            settings,
+            queue,
+            seen,
+        }))
+    }
+
+    /// Remove the saved crawl
+    pub async fn clear<'a, A>(conn: A) -> Result<(), sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        Self::default().save(conn).await
+    }
+}
+
+impl ClippyCrawlChanges {
+    /// Apply the changes to the saved crawl, and empty them
+    pub async fn save<'a, A>(&mut self, conn: A) -> Result<(), sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+        let mut trans = Acquire::begin(&mut *conn).await?;
+
+        self.apply(&mut *trans).await?;
+        trans.commit().await?;
+
+        *self = Self::default();
+        Ok(())
+    }
+
+    async fn apply(&self, conn: &mut sqlx::SqliteConnection) -> Result<(), sqlx::Error> {
+        for node in &self.added {
+            sqlx::query("INSERT OR REPLACE INTO clippy_crawl_queue VALUES (?, ?, ?, ?);")
+                .bind(node.position)
+                .bind(&node.entity_type)
+                .bind(&node.mbid)
+                .bind(node.depth)
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        for position in &self.removed {
+            sqlx::query("DELETE FROM clippy_crawl_queue WHERE position = ?;")
+                .bind(position)
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        for (mbid, depth) in &self.seen {
+            sqlx::query("INSERT OR REPLACE INTO clippy_crawl_seen VALUES (?, ?);")
+                .bind(mbid)
+                .bind(depth)
+                .execute(&mut *conn)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use sqlx::Acquire;
+use sqlx::Sqlite;
+
+use crate::models::local_file::LocalFile;
+
+impl LocalFile {
+    pub async fn upsert<'a, A>(self, conn: A) -> Result<LocalFile, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query_as(
+            "
+INSERT INTO
+    local_file
+VALUES
+    (NULL, ?, ?, ?)
+ON CONFLICT DO
+UPDATE
+SET
+    `recording_mbid` = excluded.`recording_mbid`,
+    `modified_at` = excluded.`modified_at` RETURNING *;",
+        )
+        .bind(self.path)
+        .bind(self.recording_mbid)
+        .bind(self.modified_at)
+        .fetch_one(&mut *conn)
+        .await
+    }
+
+    /// Save multiple files at once
+    pub async fn upsert_all<'a, A>(conn: A, files: Vec<LocalFile>) -> Result<(), sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+        let mut trans = Acquire::begin(&mut *conn).await?;
+
+        for file in files {
+            file.upsert(&mut *trans).await?;
+        }
+
+        trans.commit().await
+    }
+
+    pub async fn find_all<'a, A>(conn: A) -> Result<Vec<LocalFile>, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query_as("SELECT * FROM local_file;")
+            .fetch_all(&mut *conn)
+            .await
+    }
+
+    pub async fn find_by_path<'a, A>(conn: A, path: &str) -> Result<Option<LocalFile>, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query_as("SELECT * FROM local_file WHERE path = ?;")
+            .bind(path)
+            .fetch_optional(&mut *conn)
+            .await
+    }
+
+    pub async fn find_by_recording<'a, A>(
+        conn: A,
+        recording_mbid: &str,
+    ) -> Result<Vec<LocalFile>, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query_as("SELECT * FROM local_file WHERE recording_mbid = ?;")
+            .bind(recording_mbid)
+            .fetch_all(&mut *conn)
+            .await
+    }
+
+    /// Delete multiple files at once
+    pub async fn delete_all<'a, A>(conn: A, files: &[LocalFile]) -> Result<(), sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+        let mut trans = Acquire::begin(&mut *conn).await?;
+
+        for file in files {
+            file.delete(&mut *trans).await?;
+        }
+
+        trans.commit().await
+    }
+
+    pub async fn delete<'a, A>(&self, conn: A) -> Result<(), sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query("DELETE FROM local_file WHERE id = ?;")
+            .bind(self.id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
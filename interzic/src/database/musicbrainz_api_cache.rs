@@ -0,0 +1,66 @@
+use sqlx::Acquire;
+use sqlx::Sqlite;
+
+use crate::models::musicbrainz_api_cache::MusicbrainzApiCache;
+
+impl MusicbrainzApiCache {
+    pub async fn upsert<'a, A>(&self, conn: A) -> Result<(), sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query(
+            "
+INSERT INTO
+    musicbrainz_api_cache
+VALUES
+    (?, ?, ?, ?)
+ON CONFLICT DO UPDATE SET
+    data = excluded.data,
+    fetched_at = excluded.fetched_at;",
+        )
+        .bind(&self.entity_type)
+        .bind(&self.mbid)
+        .bind(&self.data)
+        .bind(self.fetched_at)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get the cached response of an entity, if any
+    pub async fn find<'a, A>(
+        conn: A,
+        entity_type: &str,
+        mbid: &str,
+    ) -> Result<Option<MusicbrainzApiCache>, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query_as("SELECT * FROM musicbrainz_api_cache WHERE entity_type = ? AND mbid = ?;")
+            .bind(entity_type)
+            .bind(mbid)
+            .fetch_optional(&mut *conn)
+            .await
+    }
+
+    /// Remove the cached response of an entity, so it gets fetched again
+    pub async fn delete<'a, A>(conn: A, entity_type: &str, mbid: &str) -> Result<(), sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query("DELETE FROM musicbrainz_api_cache WHERE entity_type = ? AND mbid = ?;")
+            .bind(entity_type)
+            .bind(mbid)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+use sqlx::Acquire;
+use sqlx::Sqlite;
+
+use crate::models::messy_recording::MessyRecording;
+use crate::models::playlist_history::PlaylistHistory;
+
+impl PlaylistHistory {
+    pub async fn insert<'a, A>(self, conn: A) -> Result<PlaylistHistory, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query_as(
+            "
+INSERT INTO
+    playlist_history
+VALUES
+    (NULL, ?, ?, ?, ?, ?, ?, ?) RETURNING *;",
+        )
+        .bind(self.title)
+        .bind(self.description)
+        .bind(self.kind)
+        .bind(self.parameters)
+        .bind(self.target)
+        .bind(self.created_at)
+        .bind(self.user)
+        .fetch_one(&mut *conn)
+        .await
+    }
+
+    /// Save the recordings of the playlist, in order. The recordings must have been upserted beforehand
+    pub async fn set_recordings<'a, A>(
+        &self,
+        conn: A,
+        recordings: &[MessyRecording],
+    ) -> Result<(), sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+        let mut trans = Acquire::begin(&mut *conn).await?;
+
+        sqlx::query("DELETE FROM playlist_history_recording WHERE playlist_id = ?;")
+            .bind(self.id)
+            .execute(&mut *trans)
+            .await?;
+
+        for (position, recording) in recordings.iter().enumerate() {
+            sqlx::query("INSERT INTO playlist_history_recording VALUES (?, ?, ?);")
+                .bind(self.id)
+                .bind(position as i64)
+                .bind(recording.id)
+                .execute(&mut *trans)
+                .await?;
+        }
+
+        trans.commit().await
+    }
+
+    pub async fn find_by_id<'a, A>(conn: A, id: i64) -> Result<Option<PlaylistHistory>, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query_as("SELECT * FROM playlist_history WHERE id = ?;")
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await
+    }
+
+    /// Get the latest playlists, newest first
+    pub async fn find_latest<'a, A>(
+        conn: A,
+        limit: u64,
+    ) -> Result<Vec<PlaylistHistory>, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query_as("SELECT * FROM playlist_history ORDER BY created_at DESC, id DESC LIMIT ?;")
+            .bind(limit as i64)
+            .fetch_all(&mut *conn)
+            .await
+    }
+
+    /// Get the recordings of the playlist, in order
+    pub async fn get_recordings<'a, A>(&self, conn: A) -> Result<Vec<MessyRecording>, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query_as(
+            "
+SELECT
+    recording.*
+FROM
+    playlist_history_recording
+    INNER JOIN recording ON playlist_history_recording.recording_id = recording.id
+WHERE
+    playlist_history_recording.playlist_id = ?
+ORDER BY
+    playlist_history_recording.position;",
+        )
+        .bind(self.id)
+        .fetch_all(&mut *conn)
+        .await
+    }
+
+    /// Get the MBIDs of the recordings that were put in a playlist of the user after the date (as a unix timestamp)
+    pub async fn find_recording_mbids_since<'a, A>(
+        conn: A,
+        user: &str,
+        since: i64,
+    ) -> Result<Vec<String>, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query_scalar(
+            "
+SELECT DISTINCT
+    recording.mbid
+FROM
+    playlist_history
+    INNER JOIN playlist_history_recording ON playlist_history.id = playlist_history_recording.playlist_id
+    INNER JOIN recording ON playlist_history_recording.recording_id = recording.id
+WHERE
+    playlist_history.user = ?
+    AND playlist_history.created_at >= ?
+    AND recording.mbid IS NOT NULL;",
+        )
+        .bind(user)
+        .bind(since)
+        .fetch_all(&mut *conn)
+        .await
+    }
+}
@@ -0,0 +1,77 @@
+use sqlx::Acquire;
+use sqlx::Sqlite;
+
+use crate::models::popularity_cache::PopularityCache;
+
+impl PopularityCache {
+    pub async fn upsert<'a, A>(&self, conn: A) -> Result<(), sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query(
+            "
+INSERT INTO
+    popularity_cache
+VALUES
+    (?, ?, ?, ?, ?)
+ON CONFLICT DO UPDATE SET
+    total_listen_count = excluded.total_listen_count,
+    total_user_count = excluded.total_user_count,
+    fetched_at = excluded.fetched_at;",
+        )
+        .bind(&self.entity_type)
+        .bind(&self.mbid)
+        .bind(self.total_listen_count)
+        .bind(self.total_user_count)
+        .bind(self.fetched_at)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Save multiple cache entries at once
+    pub async fn upsert_all<'a, A>(conn: A, entries: &[PopularityCache]) -> Result<(), sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+        let mut trans = Acquire::begin(&mut *conn).await?;
+
+        for entry in entries {
+            entry.upsert(&mut *trans).await?;
+        }
+
+        trans.commit().await
+    }
+
+    /// Get the cached entries of the given MBIDs. MBIDs without cache entries are ignored
+    pub async fn find_by_mbids<'a, A>(
+        conn: A,
+        entity_type: &str,
+        mbids: &[String],
+    ) -> Result<Vec<PopularityCache>, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+        let mbids = serde_json::to_string(mbids).expect("A list of strings is always serializable");
+
+        sqlx::query_as(
+            "
+SELECT
+    popularity_cache.*
+FROM
+    popularity_cache
+WHERE
+    entity_type = ?
+    AND mbid IN (SELECT value FROM json_each(?));",
+        )
+        .bind(entity_type)
+        .bind(mbids)
+        .fetch_all(&mut *conn)
+        .await
+    }
+}
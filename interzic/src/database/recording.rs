@@ -14,19 +14,21 @@ impl MessyRecording {
             INSERT INTO
                 recording
             VALUES
-                (NULL, ?, ?, ?, ?)
+                (NULL, ?, ?, ?, ?, ?)
             ON CONFLICT DO
             UPDATE
             SET
                 `title` = excluded.`title`,
                 `artist_credits` = excluded.`artist_credits`,
                 `release` = excluded.`release`,
-                `mbid` = excluded.`mbid` RETURNING *;",
+                `mbid` = excluded.`mbid`,
+                `duration` = excluded.`duration` RETURNING *;",
         )
         .bind(self.title)
         .bind(self.artist_credits)
         .bind(self.release)
         .bind(self.mbid)
+        .bind(self.duration)
         .fetch_one(&mut *conn)
         .await
     }
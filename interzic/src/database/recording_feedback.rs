@@ -0,0 +1,76 @@
+use sqlx::Acquire;
+use sqlx::Sqlite;
+
+use crate::models::recording_feedback::RecordingFeedback;
+
+impl RecordingFeedback {
+    pub async fn upsert<'a, A>(&self, conn: A) -> Result<(), sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query(
+            "
+INSERT INTO
+    recording_feedback
+VALUES
+    (LOWER(?), ?, ?, ?)
+ON CONFLICT DO UPDATE SET
+    score = excluded.score,
+    created_at = excluded.created_at;",
+        )
+        .bind(&self.username)
+        .bind(&self.recording_mbid)
+        .bind(self.score)
+        .bind(self.created_at)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Replace all the feedback of the user
+    pub async fn replace_user_feedback<'a, A>(
+        conn: A,
+        username: &str,
+        feedback: &[RecordingFeedback],
+    ) -> Result<(), sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+        let mut trans = Acquire::begin(&mut *conn).await?;
+
+        sqlx::query("DELETE FROM recording_feedback WHERE username = LOWER(?);")
+            .bind(username)
+            .execute(&mut *trans)
+            .await?;
+
+        for item in feedback {
+            item.upsert(&mut *trans).await?;
+        }
+
+        trans.commit().await
+    }
+
+    /// Get the MBIDs of the recordings the user gave this score to
+    pub async fn find_mbids_by_score<'a, A>(
+        conn: A,
+        username: &str,
+        score: i64,
+    ) -> Result<Vec<String>, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+
+        sqlx::query_scalar(
+            "SELECT recording_mbid FROM recording_feedback WHERE username = LOWER(?) AND score = ?;",
+        )
+        .bind(username)
+        .bind(score)
+        .fetch_all(&mut *conn)
+        .await
+    }
+}
@@ -18,9 +18,24 @@ pub enum Error {
     #[error(transparent)]
     ListenBrainzError(#[from] listenbrainz::Error),
 
+    #[error("Error while sending a request to Listenbrainz")]
+    ListenBrainzRequestError(#[from] reqwest::Error),
+
     #[error(transparent)]
     MigrationError(#[from] sqlx::migrate::MigrateError),
 
+    #[error("Couldn't write the playlist file")]
+    PlaylistFileError(io::Error),
+
+    #[error("Couldn't serialize the playlist")]
+    PlaylistSerializationError(serde_json::Error),
+
+    #[error("Couldn't read the playlist file")]
+    PlaylistFileReadError(io::Error),
+
+    #[error("Couldn't parse the playlist")]
+    PlaylistDeserializationError(serde_json::Error),
+
     #[error("The client is missing: {0}")]
     ClientBuildingError(String),
 
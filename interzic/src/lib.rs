@@ -1,3 +1,10 @@
+//! Interzic translates recordings between music services, and keeps the mappings in its own SQLite database.
+//!
+//! This database is also where Alistral keeps the local state that isn't MusicBrainz or ListenBrainz data:
+//! the playlist history, the ListenBrainz feedback, the popularity cache, and the clippy crawl progress and API cache.
+//! The `musicbrainz_db_lite` schema isn't Alistral's to extend, so this is the one migrated database Alistral owns.
+//! Those tables are Alistral's, and other users of the crate can ignore them.
+
 pub mod client;
 pub mod database;
 pub mod error;
@@ -0,0 +1,49 @@
+use sqlx::prelude::FromRow;
+
+/// An entity waiting to be checked by clippy
+#[derive(Clone, FromRow, Debug)]
+pub struct ClippyCrawlNode {
+    /// The place of the node in the queue. Nodes added at the front get a smaller position than the first one
+    pub position: i64,
+
+    /// The type of entity. Ex: `recording`, `artist`
+    pub entity_type: String,
+    pub mbid: String,
+
+    /// How many relations away from the start entities it is
+    pub depth: i64,
+}
+
+/// How the crawl goes through the entities
+#[derive(Clone, FromRow, Debug, Default)]
+pub struct ClippyCrawlSettings {
+    /// Whether to check FILO (first in, last out) instead of FIFO (first in, first out)
+    pub new_first: bool,
+    pub max_depth: Option<i64>,
+    pub max_count: Option<i64>,
+}
+
+/// The progress of a clippy crawl, saved to resume it later
+#[derive(Clone, Debug, Default)]
+pub struct ClippyCrawlState {
+    pub settings: ClippyCrawlSettings,
+
+    /// The entities left to check, in queue order
+    pub queue: Vec<ClippyCrawlNode>,
+
+    /// The MBIDs of the entities already checked, with the shallowest depth they got expanded from
+    pub seen: Vec<(String, i64)>,
+}
+
+/// The changes made to the crawl since its last save
+#[derive(Clone, Debug, Default)]
+pub struct ClippyCrawlChanges {
+    /// The nodes added to the queue
+    pub added: Vec<ClippyCrawlNode>,
+
+    /// The positions of the nodes removed from the queue
+    pub removed: Vec<i64>,
+
+    /// The checked entities, with their new depth
+    pub seen: Vec<(String, i64)>,
+}
@@ -0,0 +1,14 @@
+use sqlx::prelude::FromRow;
+
+/// An audio file on the local filesystem, associated to the MBID found in its tags
+#[derive(Clone, FromRow, Debug)]
+pub struct LocalFile {
+    pub id: i64,
+    pub path: String,
+
+    /// The MBID found in the tags. Files without MBID are kept to not read them again
+    pub recording_mbid: Option<String>,
+
+    /// The last modification time of the file when it got indexed, as a unix timestamp
+    pub modified_at: i64,
+}
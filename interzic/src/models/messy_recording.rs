@@ -10,6 +10,9 @@ pub struct MessyRecording {
     pub artist_credits: String,
     pub release: Option<String>,
     pub mbid: Option<String>,
+
+    /// The duration of the recording, in milliseconds
+    pub duration: Option<i64>,
 }
 
 impl MessyRecording {
@@ -33,6 +36,9 @@ impl MessyRecording {
             title: recording.title,
             artist_credits: credits.to_string(),
             release: release.into_iter().next().map(|r| r.title),
+            duration: recording
+                .length_as_duration()
+                .map(|duration| duration.num_milliseconds()),
         })
     }
 }
@@ -1,4 +1,12 @@
 pub mod external_id;
+pub mod local_file;
 pub mod messy_recording;
 pub mod playlist_stub;
 pub mod services;
+
+// Alistral's local state. See the crate documentation
+pub mod clippy_crawl;
+pub mod musicbrainz_api_cache;
+pub mod playlist_history;
+pub mod popularity_cache;
+pub mod recording_feedback;
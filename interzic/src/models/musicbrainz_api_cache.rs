@@ -0,0 +1,15 @@
+use sqlx::prelude::FromRow;
+
+/// A response of the MusicBrainz API, for the data that the local database doesn't have
+#[derive(Clone, FromRow, Debug)]
+pub struct MusicbrainzApiCache {
+    /// The type of entity. Ex: `release`, `work`
+    pub entity_type: String,
+    pub mbid: String,
+
+    /// The fetched entity, as JSON
+    pub data: String,
+
+    /// When the entity got fetched, as a unix timestamp
+    pub fetched_at: i64,
+}
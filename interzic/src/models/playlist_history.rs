@@ -0,0 +1,24 @@
+use sqlx::prelude::FromRow;
+
+/// A playlist that got generated and exported
+#[derive(Clone, FromRow, Debug)]
+pub struct PlaylistHistory {
+    pub id: i64,
+    pub title: String,
+    pub description: String,
+
+    /// What generated the playlist. Ex: the type of radio
+    pub kind: String,
+
+    /// The parameters used to generate the playlist, as JSON
+    pub parameters: String,
+
+    /// Where the playlist got exported to
+    pub target: String,
+
+    /// When the playlist got generated, as a unix timestamp
+    pub created_at: i64,
+
+    /// The user the playlist got generated for
+    pub user: Option<String>,
+}
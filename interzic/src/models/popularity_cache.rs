@@ -0,0 +1,15 @@
+use sqlx::prelude::FromRow;
+
+/// The worldwide listen statistics of an entity, as fetched from the ListenBrainz popularity API
+#[derive(Clone, FromRow, Debug)]
+pub struct PopularityCache {
+    /// The type of entity. Ex: `recording`, `release_group`
+    pub entity_type: String,
+    pub mbid: String,
+
+    pub total_listen_count: Option<i64>,
+    pub total_user_count: Option<i64>,
+
+    /// When the statistics got fetched, as a unix timestamp
+    pub fetched_at: i64,
+}
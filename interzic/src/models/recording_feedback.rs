@@ -0,0 +1,25 @@
+use sqlx::prelude::FromRow;
+
+/// The love (1) or hate (-1) feedback a user gave to a recording on ListenBrainz
+#[derive(Clone, FromRow, Debug)]
+pub struct RecordingFeedback {
+    /// The name of the user, in lowercase
+    pub username: String,
+    pub recording_mbid: String,
+
+    /// 1 if the recording is loved, -1 if it is hated
+    pub score: i64,
+
+    /// When the feedback got given, as a unix timestamp
+    pub created_at: i64,
+}
+
+impl RecordingFeedback {
+    pub fn is_loved(&self) -> bool {
+        self.score > 0
+    }
+
+    pub fn is_hated(&self) -> bool {
+        self.score < 0
+    }
+}
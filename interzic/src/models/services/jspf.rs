@@ -0,0 +1,146 @@
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+
+use crate::models::messy_recording::MessyRecording;
+use crate::models::playlist_stub::PlaylistStub;
+
+pub struct Jspf;
+
+impl Jspf {
+    /// Write the playlist as a JSPF file
+    pub fn save_playlist(
+        playlist: PlaylistStub,
+        creator: Option<String>,
+        path: &Path,
+    ) -> Result<(), crate::Error> {
+        let file = File::create(path).map_err(crate::Error::PlaylistFileError)?;
+        serde_json::to_writer_pretty(file, &Self::into_jspf(playlist, creator))
+            .map_err(crate::Error::PlaylistSerializationError)?;
+
+        Ok(())
+    }
+
+    /// Read a JSPF file
+    pub fn load_playlist(path: &Path) -> Result<JspfDocument, crate::Error> {
+        let file = File::open(path).map_err(crate::Error::PlaylistFileReadError)?;
+        serde_json::from_reader(BufReader::new(file))
+            .map_err(crate::Error::PlaylistDeserializationError)
+    }
+
+    pub fn into_jspf(playlist: PlaylistStub, creator: Option<String>) -> JspfDocument {
+        JspfDocument {
+            playlist: JspfPlaylist {
+                title: playlist.title,
+                creator,
+                annotation: Some(playlist.description),
+                track: playlist
+                    .recordings
+                    .into_iter()
+                    .map(JspfTrack::from)
+                    .collect(),
+            },
+        }
+    }
+}
+
+/// The root of a JSPF file. See <https://www.xspf.org/jspf>
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JspfDocument {
+    pub playlist: JspfPlaylist,
+}
+
+impl JspfDocument {
+    /// Get the MBIDs of the MusicBrainz recordings of the playlist, in order. Tracks without a recording identifier are skipped
+    pub fn get_recording_mbids(&self) -> Vec<String> {
+        self.playlist
+            .track
+            .iter()
+            .filter_map(|track| {
+                track.identifier.iter().find_map(|identifier| {
+                    identifier
+                        .split_once("musicbrainz.org/recording/")
+                        .map(|(_, mbid)| mbid.trim_end_matches('/').to_string())
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JspfPlaylist {
+    #[serde(default)]
+    pub title: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub creator: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<String>,
+
+    #[serde(default)]
+    pub track: Vec<JspfTrack>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JspfTrack {
+    #[serde(default)]
+    pub title: String,
+
+    /// The artist credits of the track
+    #[serde(default)]
+    pub creator: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub album: Option<String>,
+
+    /// The duration of the track, in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<i64>,
+
+    /// The canonical identifiers of the track. For MusicBrainz recordings, this is the recording's URL
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_identifiers"
+    )]
+    pub identifier: Vec<String>,
+}
+
+impl From<MessyRecording> for JspfTrack {
+    fn from(value: MessyRecording) -> Self {
+        Self {
+            identifier: value
+                .mbid
+                .into_iter()
+                .map(|id| format!("https://musicbrainz.org/recording/{id}"))
+                .collect(),
+            title: value.title,
+            creator: value.artist_credits,
+            album: value.release,
+            duration: value.duration,
+        }
+    }
+}
+
+/// Older JSPF files (and older ListenBrainz versions) have a single identifier instead of a list
+fn deserialize_identifiers<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Identifiers {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Identifiers::deserialize(deserializer)? {
+        Identifiers::One(identifier) => vec![identifier],
+        Identifiers::Many(identifiers) => identifiers,
+    })
+}
@@ -4,9 +4,16 @@ use listenbrainz::raw::request::PlaylistCreatePlaylistExtension;
 use listenbrainz::raw::request::PlaylistCreatePlaylistExtensionInner;
 use listenbrainz::raw::request::PlaylistCreatePlaylistTrack;
 
+use serde::Deserialize;
+use serde_json::json;
+
 use crate::models::playlist_stub::PlaylistStub;
+use crate::models::services::jspf::JspfDocument;
 use crate::InterzicClient;
 
+/// The maximum number of tracks that can be added to a playlist in a single request
+const MAX_TRACKS_PER_ADD: usize = 100;
+
 pub struct Listenbrainz;
 
 impl Listenbrainz {
@@ -22,6 +29,126 @@ impl Listenbrainz {
             .playlist_mbid)
     }
 
+    /// Replace the title, description and tracks of an existing playlist.
+    ///
+    /// The new tracks are added before removing the old ones, so a failure never leaves the playlist empty
+    pub async fn replace_playlist(
+        client: &InterzicClient,
+        playlist_mbid: &str,
+        playlist: PlaylistStub,
+        username: String,
+        token: &str,
+    ) -> Result<String, crate::Error> {
+        let http = client.http_client();
+        let auth = format!("Token {token}");
+
+        let current: PlaylistResponse = http
+            .get(client.listenbrainz_endpoint(&format!("playlist/{playlist_mbid}")))
+            .header("Authorization", &auth)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let old_track_count = current.playlist.track.len();
+
+        let jspf = Self::into_jspf(playlist, username).playlist;
+
+        http.post(client.listenbrainz_endpoint(&format!("playlist/edit/{playlist_mbid}")))
+            .header("Authorization", &auth)
+            .json(&json!({"playlist": {"title": jspf.title, "annotation": jspf.annotation}}))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        // The new tracks get appended after the old ones
+        for tracks in jspf.track.chunks(MAX_TRACKS_PER_ADD) {
+            let tracks = tracks
+                .iter()
+                .map(|track| json!({"identifier": track.identifier}))
+                .collect::<Vec<_>>();
+
+            http.post(client.listenbrainz_endpoint(&format!("playlist/{playlist_mbid}/item/add")))
+                .header("Authorization", &auth)
+                .json(&json!({"playlist": {"track": tracks}}))
+                .send()
+                .await?
+                .error_for_status()?;
+        }
+
+        // Then remove the old tracks at the start
+        if old_track_count != 0 {
+            http.post(
+                client.listenbrainz_endpoint(&format!("playlist/{playlist_mbid}/item/delete")),
+            )
+            .header("Authorization", &auth)
+            .json(&json!({"index": 0, "count": old_track_count}))
+            .send()
+            .await?
+            .error_for_status()?;
+        }
+
+        Ok(playlist_mbid.to_string())
+    }
+
+    /// Fetch a playlist as JSPF. Private playlists require the token of their owner
+    pub async fn fetch_playlist(
+        client: &InterzicClient,
+        playlist_mbid: &str,
+        token: Option<&str>,
+    ) -> Result<JspfDocument, crate::Error> {
+        let mut request = client
+            .http_client()
+            .get(client.listenbrainz_endpoint(&format!("playlist/{playlist_mbid}")));
+
+        if let Some(token) = token {
+            request = request.header("Authorization", format!("Token {token}"));
+        }
+
+        Ok(request.send().await?.error_for_status()?.json().await?)
+    }
+
+    /// Find the MBID of a playlist of the user by its title
+    pub async fn find_playlist_by_name(
+        client: &InterzicClient,
+        username: &str,
+        title: &str,
+        token: &str,
+    ) -> Result<Option<String>, crate::Error> {
+        let http = client.http_client();
+        let mut offset = 0;
+
+        loop {
+            let page: UserPlaylistsResponse = http
+                .get(client.listenbrainz_endpoint(&format!("user/{username}/playlists")))
+                .header("Authorization", format!("Token {token}"))
+                .query(&[("count", 100), ("offset", offset)])
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            if let Some(found) = page
+                .playlists
+                .iter()
+                .find(|item| item.playlist.title == title)
+            {
+                return Ok(found
+                    .playlist
+                    .identifier
+                    .rsplit('/')
+                    .next()
+                    .map(|mbid| mbid.to_string()));
+            }
+
+            offset += page.playlists.len();
+            if page.playlists.is_empty() || offset >= page.playlist_count {
+                return Ok(None);
+            }
+        }
+    }
+
     pub fn into_jspf(playlist: PlaylistStub, username: String) -> PlaylistCreate {
         PlaylistCreate {
             playlist: PlaylistCreatePlaylist {
@@ -52,3 +179,26 @@ impl Listenbrainz {
         }
     }
 }
+
+#[derive(Debug, Deserialize)]
+struct PlaylistResponse {
+    playlist: PlaylistResponsePlaylist,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlaylistResponsePlaylist {
+    #[serde(default)]
+    identifier: String,
+
+    #[serde(default)]
+    title: String,
+
+    #[serde(default)]
+    track: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UserPlaylistsResponse {
+    playlists: Vec<PlaylistResponse>,
+    playlist_count: usize,
+}
@@ -0,0 +1,266 @@
+use core::fmt::Write as _;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+use lofty::file::TaggedFileExt as _;
+use lofty::tag::ItemKey;
+use tracing::debug;
+use tracing::instrument;
+use tuillez::pg_spinner;
+use walkdir::WalkDir;
+
+use crate::models::local_file::LocalFile;
+use crate::models::messy_recording::MessyRecording;
+use crate::models::playlist_stub::PlaylistStub;
+use crate::InterzicClient;
+
+pub struct LocalFiles;
+
+impl LocalFiles {
+    /// Scan a directory for audio files, and index them by the recording MBID found in their tags.
+    ///
+    /// Only the tags of new and modified files are read. The files are compared by path and modification time
+    #[instrument(skip(client), fields(indicatif.pb_show = tracing::field::Empty))]
+    pub async fn index_directory(
+        client: &InterzicClient,
+        directory: &Path,
+    ) -> Result<(), crate::Error> {
+        pg_spinner!("Indexing local files of {}", directory.display());
+
+        // Load the whole index at once, as querying each file is slow on big libraries
+        let mut cached: HashMap<String, LocalFile> = LocalFile::find_all(&client.database_client)
+            .await?
+            .into_iter()
+            .map(|file| (file.path.clone(), file))
+            .collect();
+        let mut changed = Vec::new();
+
+        for entry in WalkDir::new(directory)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(Result::ok)
+        {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let path = entry.path();
+            let Some(modified_at) = get_modification_time(path) else {
+                continue;
+            };
+
+            let path_str = path.to_string_lossy().to_string();
+            if cached
+                .remove(&path_str)
+                .is_some_and(|file| file.modified_at == modified_at)
+            {
+                continue;
+            }
+
+            changed.push(LocalFile {
+                id: 0,
+                path: path_str,
+                recording_mbid: read_recording_mbid(path),
+                modified_at,
+            });
+        }
+
+        LocalFile::upsert_all(&client.database_client, changed).await?;
+
+        // The files of the directory that weren't found got moved or deleted since the last scan
+        let removed = cached
+            .into_values()
+            .filter(|file| Path::new(&file.path).starts_with(directory))
+            .collect::<Vec<_>>();
+        LocalFile::delete_all(&client.database_client, &removed).await?;
+
+        Ok(())
+    }
+
+    /// Get the path of a local file containing the recording
+    pub async fn get_file_path(
+        client: &InterzicClient,
+        recording: &MessyRecording,
+    ) -> Result<Option<PathBuf>, crate::Error> {
+        let Some(mbid) = &recording.mbid else {
+            return Ok(None);
+        };
+
+        for file in LocalFile::find_by_recording(&client.database_client, mbid).await? {
+            let path = PathBuf::from(&file.path);
+
+            if path.exists() {
+                return Ok(Some(path));
+            }
+
+            // The file has been moved or deleted since the last scan
+            file.delete(&client.database_client).await?;
+        }
+
+        Ok(None)
+    }
+
+    /// Write the playlist as a file of local paths. Returns the recordings that have no local file
+    pub async fn save_playlist(
+        client: &InterzicClient,
+        playlist: PlaylistStub,
+        format: LocalPlaylistFormat,
+        path: &Path,
+    ) -> Result<Vec<MessyRecording>, crate::Error> {
+        let mut found = Vec::new();
+        let mut missing = Vec::new();
+
+        for recording in playlist.recordings {
+            match Self::get_file_path(client, &recording).await? {
+                Some(file) => found.push((recording, file)),
+                None => missing.push(recording),
+            }
+        }
+
+        let content = match format {
+            LocalPlaylistFormat::M3u8 => to_m3u8(&playlist.title, &found),
+            LocalPlaylistFormat::Xspf => to_xspf(&playlist.title, &playlist.description, &found),
+        };
+
+        fs::write(path, content).map_err(crate::Error::PlaylistFileError)?;
+
+        Ok(missing)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalPlaylistFormat {
+    M3u8,
+    Xspf,
+}
+
+fn get_modification_time(path: &Path) -> Option<i64> {
+    let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+
+    modified
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|dur| dur.as_secs().try_into().ok())
+}
+
+fn read_recording_mbid(path: &Path) -> Option<String> {
+    let tagged_file = match lofty::read_from_path(path) {
+        Ok(val) => val,
+        Err(err) => {
+            debug!("Skipping {}: {err}", path.display());
+            return None;
+        }
+    };
+
+    tagged_file
+        .tags()
+        .iter()
+        .find_map(|tag| tag.get_string(&ItemKey::MusicBrainzRecordingId))
+        .map(|mbid| mbid.trim().to_string())
+}
+
+fn to_m3u8(title: &str, tracks: &[(MessyRecording, PathBuf)]) -> String {
+    let mut out = String::new();
+    writeln!(out, "#EXTM3U").unwrap();
+    writeln!(out, "#PLAYLIST:{title}").unwrap();
+
+    for (recording, path) in tracks {
+        let seconds = recording.duration.map(|ms| ms / 1000).unwrap_or(-1);
+        writeln!(
+            out,
+            "#EXTINF:{seconds},{} - {}",
+            recording.artist_credits, recording.title
+        )
+        .unwrap();
+        writeln!(out, "{}", path.display()).unwrap();
+    }
+
+    out
+}
+
+fn to_xspf(title: &str, annotation: &str, tracks: &[(MessyRecording, PathBuf)]) -> String {
+    let mut out = String::new();
+    writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+    writeln!(
+        out,
+        r#"<playlist version="1" xmlns="http://xspf.org/ns/0/">"#
+    )
+    .unwrap();
+    writeln!(out, "  <title>{}</title>", escape_xml(title)).unwrap();
+    writeln!(out, "  <annotation>{}</annotation>", escape_xml(annotation)).unwrap();
+    writeln!(out, "  <trackList>").unwrap();
+
+    for (recording, path) in tracks {
+        writeln!(out, "    <track>").unwrap();
+        writeln!(
+            out,
+            "      <location>{}</location>",
+            escape_xml(&path_to_file_uri(path))
+        )
+        .unwrap();
+
+        if let Some(mbid) = &recording.mbid {
+            writeln!(
+                out,
+                "      <identifier>https://musicbrainz.org/recording/{mbid}</identifier>"
+            )
+            .unwrap();
+        }
+
+        writeln!(out, "      <title>{}</title>", escape_xml(&recording.title)).unwrap();
+        writeln!(
+            out,
+            "      <creator>{}</creator>",
+            escape_xml(&recording.artist_credits)
+        )
+        .unwrap();
+
+        if let Some(release) = &recording.release {
+            writeln!(out, "      <album>{}</album>", escape_xml(release)).unwrap();
+        }
+
+        if let Some(duration) = recording.duration {
+            writeln!(out, "      <duration>{duration}</duration>").unwrap();
+        }
+
+        writeln!(out, "    </track>").unwrap();
+    }
+
+    writeln!(out, "  </trackList>").unwrap();
+    writeln!(out, "</playlist>").unwrap();
+
+    out
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Convert a path into a `file://` URI, percent encoding the reserved characters
+fn path_to_file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = "file://".to_string();
+
+    // Windows paths don't start with a slash
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(byte as char);
+            }
+            _ => write!(uri, "%{byte:02X}").unwrap(),
+        }
+    }
+
+    uri
+}
@@ -1,3 +1,5 @@
+pub mod jspf;
 pub mod listenbrainz;
+pub mod local_files;
 pub mod musicbrainz;
 pub mod youtube;
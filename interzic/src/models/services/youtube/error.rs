@@ -18,6 +18,14 @@ pub enum InterzicYoutubeError {
     #[error(transparent)]
     PlaylistInsertError(YoutubeError),
 
+    //#[error("An error happened when updating a playlist")]
+    #[error(transparent)]
+    PlaylistUpdateError(YoutubeError),
+
+    //#[error("Couldn't list the playlists or the playlist items")]
+    #[error(transparent)]
+    PlaylistListError(YoutubeError),
+
     //#[error("Couldn't search the recording")]
     #[error(transparent)]
     RecordingSearchError(YoutubeError),
@@ -29,6 +37,8 @@ impl InterzicYoutubeError {
             Self::ApiError(val) => Some(val),
             Self::PlaylistCreateError(val) => Some(val),
             Self::PlaylistInsertError(val) => Some(val),
+            Self::PlaylistUpdateError(val) => Some(val),
+            Self::PlaylistListError(val) => Some(val),
             Self::RecordingSearchError(val) => Some(val),
             Self::MissingYoutubeClient() => None,
         }
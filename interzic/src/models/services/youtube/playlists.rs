@@ -62,6 +62,133 @@ impl Youtube {
         Ok(playlist_id)
     }
 
+    /// Replace the title, description and videos of an existing playlist
+    pub async fn replace_playlist(
+        client: &InterzicClient,
+        playlist_id: String,
+        playlist: PlaylistStub,
+    ) -> Result<String, crate::Error> {
+        info!("Updating playlist");
+
+        let mut yt_playlist = playlist.clone().to_yt_playlist();
+        yt_playlist.id = Some(playlist_id.clone());
+
+        client
+            .youtube_client()?
+            .playlists()
+            .update(yt_playlist)
+            .add_part("snippet")
+            .doit()
+            .await
+            .map_err(YoutubeError::from)
+            .map_err(InterzicYoutubeError::PlaylistUpdateError)?;
+
+        // The old videos are only removed once the new ones are added, so a failed update doesn't leave the playlist empty
+        let old_item_ids = Self::get_playlist_item_ids(client, &playlist_id).await?;
+
+        let rate_limit = RateLimiter::direct(Quota::per_second(NonZeroU32::new(1).unwrap()));
+        Self::add_recordings_to_playlist(client, playlist_id.clone(), playlist, rate_limit).await?;
+
+        info!("Removing old videos");
+        for item_id in old_item_ids {
+            client
+                .youtube_client()?
+                .playlist_items()
+                .delete(&item_id)
+                .doit()
+                .await
+                .map_err(YoutubeError::from)
+                .map_err(InterzicYoutubeError::PlaylistUpdateError)?;
+        }
+
+        Ok(playlist_id)
+    }
+
+    /// Find the id of a playlist of the user by its title
+    pub async fn find_playlist_by_name(
+        client: &InterzicClient,
+        title: &str,
+    ) -> Result<Option<String>, crate::Error> {
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut request = client
+                .youtube_client()?
+                .playlists()
+                .list(&vec!["id,snippet".to_string()])
+                .mine(true)
+                .max_results(50);
+
+            if let Some(token) = &page_token {
+                request = request.page_token(token);
+            }
+
+            let response = request
+                .doit()
+                .await
+                .map_err(YoutubeError::from)
+                .map_err(InterzicYoutubeError::PlaylistListError)?
+                .1;
+
+            let found = response.items.unwrap_or_default().into_iter().find(|p| {
+                p.snippet
+                    .as_ref()
+                    .and_then(|snippet| snippet.title.as_deref())
+                    == Some(title)
+            });
+
+            if let Some(found) = found {
+                return Ok(found.id);
+            }
+
+            match response.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    async fn get_playlist_item_ids(
+        client: &InterzicClient,
+        playlist_id: &str,
+    ) -> Result<Vec<String>, crate::Error> {
+        let mut ids = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut request = client
+                .youtube_client()?
+                .playlist_items()
+                .list(&vec!["id".to_string()])
+                .playlist_id(playlist_id)
+                .max_results(50);
+
+            if let Some(token) = &page_token {
+                request = request.page_token(token);
+            }
+
+            let response = request
+                .doit()
+                .await
+                .map_err(YoutubeError::from)
+                .map_err(InterzicYoutubeError::PlaylistListError)?
+                .1;
+
+            ids.extend(
+                response
+                    .items
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|item| item.id),
+            );
+
+            match response.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => return Ok(ids),
+            }
+        }
+    }
+
     #[instrument(skip(client), fields(indicatif.pb_show = tracing::field::Empty))]
     async fn add_recordings_to_playlist(
         client: &InterzicClient,
@@ -85,6 +85,12 @@ async fn create_interzic(
 
     client.set_musicbrainz_client(musicbrainz_rs);
     client.set_listenbrainz_client(listenbrainz);
+    client.set_listenbrainz_url(
+        Config::load_or_panic()
+            .read_or_panic()
+            .listenbrainz_url
+            .clone(),
+    );
     client.set_musicbrainz_db_lite_client(musicbrainz_db);
 
     if YT_SECRET_FILE.exists() {
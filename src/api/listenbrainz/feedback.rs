@@ -0,0 +1,72 @@
+use interzic::models::recording_feedback::RecordingFeedback;
+use tracing::instrument;
+use tuillez::pg_counted;
+use tuillez::pg_inc;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::models::data::listenbrainz::feedback::FeedbackItem;
+use crate::models::data::listenbrainz::feedback::UserFeedbackResponse;
+
+const FEEDBACK_PAGE_SIZE: u64 = 1000;
+
+/// Get all the recording feedback of the user
+#[instrument( fields(indicatif.pb_show = tracing::field::Empty))]
+pub async fn get_user_feedback(username: &str) -> Result<Vec<FeedbackItem>, crate::Error> {
+    let mut results = Vec::new();
+
+    let first_page = get_user_feedback_page(username, 0).await?;
+    pg_counted!(
+        first_page.total_count.div_ceil(FEEDBACK_PAGE_SIZE) as usize,
+        "Getting user feedback"
+    );
+    let total_count = first_page.total_count;
+    results.extend(first_page.feedback);
+    pg_inc!();
+
+    while (results.len() as u64) < total_count {
+        let page = get_user_feedback_page(username, results.len() as u64).await?;
+        if page.feedback.is_empty() {
+            break;
+        }
+
+        results.extend(page.feedback);
+        pg_inc!();
+    }
+
+    Ok(results)
+}
+
+async fn get_user_feedback_page(
+    username: &str,
+    offset: u64,
+) -> Result<UserFeedbackResponse, crate::Error> {
+    let client = &ALISTRAL_CLIENT.interzic;
+
+    Ok(client
+        .http_client()
+        .get(client.listenbrainz_endpoint(&format!("feedback/user/{username}/get-feedback")))
+        .query(&[("count", FEEDBACK_PAGE_SIZE), ("offset", offset)])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+/// Replace the cached feedback of the user by the one on ListenBrainz. Returns the number of saved feedback
+pub async fn sync_user_feedback(username: &str) -> Result<usize, crate::Error> {
+    let feedback = get_user_feedback(username)
+        .await?
+        .into_iter()
+        .filter_map(|item| item.into_recording_feedback(username))
+        .collect::<Vec<_>>();
+
+    RecordingFeedback::replace_user_feedback(
+        &ALISTRAL_CLIENT.interzic.database_client,
+        username,
+        &feedback,
+    )
+    .await?;
+
+    Ok(feedback.len())
+}
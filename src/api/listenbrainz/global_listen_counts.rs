@@ -1,34 +1,181 @@
 use std::collections::HashMap;
+use std::collections::HashSet;
 
+use chrono::Utc;
+use interzic::models::popularity_cache::PopularityCache;
+use itertools::Itertools as _;
 use tracing::instrument;
 use tuillez::pg_counted;
 use tuillez::pg_inc;
 
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::models::config::Config;
 use crate::models::data::listenbrainz::popularity::PopularityRecordingResponseItem;
+use crate::models::data::listenbrainz::popularity::PopularityReleaseGroupResponseItem;
+use crate::utils::env::in_offline_mode;
 
 #[instrument( fields(indicatif.pb_show = tracing::field::Empty))]
 pub async fn get_global_listen_counts(
     recordings: &[String],
 ) -> Result<Vec<PopularityRecordingResponseItem>, crate::Error> {
-    let mut results = Vec::new();
-    let client = reqwest::Client::new();
-    pg_counted!(recordings.len() / 999, "Getting global statistics");
+    let (mut results, missing) = get_cached_popularity("recording", recordings).await?;
 
-    for chunk in recordings.chunks(999) {
+    let client = &ALISTRAL_CLIENT.interzic;
+    pg_counted!(missing.len().div_ceil(999), "Getting global statistics");
+
+    for chunk in missing.chunks(999) {
         let mut req_body = HashMap::new();
         req_body.insert("recording_mbids", chunk);
 
         let res: Vec<PopularityRecordingResponseItem> = client
-            .post("https://api.listenbrainz.org/1/popularity/recording")
+            .http_client()
+            .post(client.listenbrainz_endpoint("popularity/recording"))
             .json(&req_body)
             .send()
             .await?
+            .error_for_status()?
             .json()
             .await?;
 
-        results.extend(res);
+        let fetched = res.into_iter().map(|item| {
+            (
+                item.recording_mbid,
+                item.total_listen_count,
+                item.total_user_count,
+            )
+        });
+        results.extend(cache_popularity("recording", chunk, fetched).await?);
         pg_inc!();
     }
 
-    Ok(results)
+    Ok(results
+        .into_iter()
+        .map(|entry| PopularityRecordingResponseItem {
+            recording_mbid: entry.mbid,
+            total_listen_count: entry.total_listen_count.map(|c| c as u64),
+            total_user_count: entry.total_user_count.map(|c| c as u64),
+        })
+        .collect())
+}
+
+#[instrument( fields(indicatif.pb_show = tracing::field::Empty))]
+pub async fn get_global_release_group_listen_counts(
+    release_groups: &[String],
+) -> Result<Vec<PopularityReleaseGroupResponseItem>, crate::Error> {
+    let (mut results, missing) = get_cached_popularity("release_group", release_groups).await?;
+
+    let client = &ALISTRAL_CLIENT.interzic;
+    pg_counted!(missing.len().div_ceil(999), "Getting global statistics");
+
+    for chunk in missing.chunks(999) {
+        let mut req_body = HashMap::new();
+        req_body.insert("release_group_mbids", chunk);
+
+        let res: Vec<PopularityReleaseGroupResponseItem> = client
+            .http_client()
+            .post(client.listenbrainz_endpoint("popularity/release-group"))
+            .json(&req_body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let fetched = res.into_iter().map(|item| {
+            (
+                item.release_group_mbid,
+                item.total_listen_count,
+                item.total_user_count,
+            )
+        });
+        results.extend(cache_popularity("release_group", chunk, fetched).await?);
+        pg_inc!();
+    }
+
+    Ok(results
+        .into_iter()
+        .map(|entry| PopularityReleaseGroupResponseItem {
+            release_group_mbid: entry.mbid,
+            total_listen_count: entry.total_listen_count.map(|c| c as u64),
+            total_user_count: entry.total_user_count.map(|c| c as u64),
+        })
+        .collect())
+}
+
+/// Get the cached popularity of the entities, and the MBIDs that need to be fetched from ListenBrainz.
+///
+/// In offline mode, any cache entry is used regardless of its age, and nothing needs fetching
+async fn get_cached_popularity(
+    entity_type: &str,
+    mbids: &[String],
+) -> Result<(Vec<PopularityCache>, Vec<String>), crate::Error> {
+    let cached = PopularityCache::find_by_mbids(
+        &ALISTRAL_CLIENT.interzic.database_client,
+        entity_type,
+        mbids,
+    )
+    .await?;
+
+    if in_offline_mode() {
+        return Ok((cached, Vec::new()));
+    }
+
+    let min_fetched_at = (Utc::now()
+        - Config::load_or_panic()
+            .read_or_panic()
+            .get_popularity_cache_duration())
+    .timestamp();
+
+    let fresh = cached
+        .into_iter()
+        .filter(|entry| entry.fetched_at >= min_fetched_at)
+        .collect_vec();
+    let fresh_mbids: HashSet<&str> = fresh.iter().map(|entry| entry.mbid.as_str()).collect();
+
+    let missing = mbids
+        .iter()
+        .filter(|mbid| !fresh_mbids.contains(mbid.as_str()))
+        .unique()
+        .cloned()
+        .collect_vec();
+
+    Ok((fresh, missing))
+}
+
+/// Save the fetched statistics (MBID, listen count, user count) of the requested entities.
+///
+/// The entities that ListenBrainz didn't return are saved without statistics, so they aren't requested again until the cache expires
+async fn cache_popularity(
+    entity_type: &str,
+    requested: &[String],
+    fetched: impl Iterator<Item = (String, Option<u64>, Option<u64>)>,
+) -> Result<Vec<PopularityCache>, crate::Error> {
+    let now = Utc::now().timestamp();
+    let mut entries: HashMap<&str, PopularityCache> = requested
+        .iter()
+        .map(|mbid| {
+            (
+                mbid.as_str(),
+                PopularityCache {
+                    entity_type: entity_type.to_string(),
+                    mbid: mbid.clone(),
+                    total_listen_count: None,
+                    total_user_count: None,
+                    fetched_at: now,
+                },
+            )
+        })
+        .collect();
+
+    for (mbid, listen_count, user_count) in fetched {
+        if let Some(entry) = entries.get_mut(mbid.as_str()) {
+            entry.total_listen_count = listen_count.map(|c| c as i64);
+            entry.total_user_count = user_count.map(|c| c as i64);
+        }
+    }
+
+    let entries = entries.into_values().collect_vec();
+    PopularityCache::upsert_all(&ALISTRAL_CLIENT.interzic.database_client, &entries).await?;
+
+    Ok(entries)
 }
@@ -1,2 +1,3 @@
+pub mod feedback;
 pub mod fresh_releases;
 pub mod global_listen_counts;
@@ -0,0 +1,62 @@
+use core::future::Future;
+
+use chrono::Duration;
+use chrono::Utc;
+use interzic::models::musicbrainz_api_cache::MusicbrainzApiCache;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::utils::env::in_offline_mode;
+
+/// How many days the cached responses are used before being fetched again
+const CACHE_DAYS: i64 = 1;
+
+/// Fetch data of an entity from the MusicBrainz API, or get it from the cache if it got fetched recently.
+///
+/// Only the data that is needed should be returned by `fetch`, as the API entities aren't meant to be serialized back.
+/// In offline mode, any cache entry is used regardless of its age, and `None` is returned for the entities that never got fetched
+pub async fn fetch_cached<T, Fut>(
+    entity_type: &str,
+    mbid: &str,
+    fetch: Fut,
+) -> Result<Option<T>, crate::Error>
+where
+    T: Serialize + DeserializeOwned,
+    Fut: Future<Output = Result<T, crate::Error>>,
+{
+    let db = &ALISTRAL_CLIENT.interzic.database_client;
+    let min_fetched_at = (Utc::now() - Duration::days(CACHE_DAYS)).timestamp();
+
+    let cached = MusicbrainzApiCache::find(db, entity_type, mbid)
+        .await?
+        .filter(|entry| in_offline_mode() || entry.fetched_at >= min_fetched_at);
+
+    // Entries that can't be read anymore are fetched again
+    if let Some(data) = cached.and_then(|entry| serde_json::from_str(&entry.data).ok()) {
+        return Ok(Some(data));
+    }
+
+    if in_offline_mode() {
+        return Ok(None);
+    }
+
+    let data = fetch.await?;
+    MusicbrainzApiCache {
+        entity_type: entity_type.to_string(),
+        mbid: mbid.to_string(),
+        data: serde_json::to_string(&data).expect("The fetched data should be serializable"),
+        fetched_at: Utc::now().timestamp(),
+    }
+    .upsert(db)
+    .await?;
+
+    Ok(Some(data))
+}
+
+/// Remove the cached response of an entity, so the next check sees the latest edits
+pub async fn clear_cached(entity_type: &str, mbid: &str) -> Result<(), crate::Error> {
+    MusicbrainzApiCache::delete(&ALISTRAL_CLIENT.interzic.database_client, entity_type, mbid)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use core::future::Future;
+
+use musicbrainz_rs::entity::BrowseResult;
+use tracing::warn;
+
+pub mod cache;
+
+/// The maximum number of entities per browse request
+pub const BROWSE_LIMIT: u8 = 100;
+
+/// Fetch all the pages of a MusicBrainz browse request.
+///
+/// `fetch_page` receives the offset of the page to fetch, and should request [`BROWSE_LIMIT`] entities
+pub async fn browse_all<T, F, Fut>(mut fetch_page: F) -> Result<Vec<T>, crate::Error>
+where
+    F: FnMut(u16) -> Fut,
+    Fut: Future<Output = Result<BrowseResult<T>, musicbrainz_rs::Error>>,
+{
+    let mut out = Vec::new();
+    let mut offset: u32 = 0;
+
+    loop {
+        // The client only takes 16 bits offsets
+        let Ok(page_offset) = u16::try_from(offset) else {
+            warn!("Stopped browsing after {offset} entities, as the rest can't be fetched");
+            break;
+        };
+
+        let page = fetch_page(page_offset).await?;
+        let fetched = page.entities.len();
+        offset += fetched as u32;
+        out.extend(page.entities);
+
+        if fetched == 0 || i64::from(offset) >= i64::from(page.count) {
+            break;
+        }
+    }
+
+    Ok(out)
+}
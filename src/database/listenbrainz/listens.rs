@@ -62,6 +62,12 @@ pub struct ListenFetchQuery {
     fetch_recordings_redirects: bool,
 
     returns: ListenFetchQueryReturn,
+
+    /// Only return the listens made after this date
+    min_listened_at: Option<DateTime<Utc>>,
+
+    /// Only return the listens made before this date
+    max_listened_at: Option<DateTime<Utc>>,
 }
 
 impl ListenFetchQuery {
@@ -79,15 +85,33 @@ impl ListenFetchQuery {
             Self::fetch_recordings_redirects(conn, &self.user).await?;
         }
 
-        match self.returns {
-            ListenFetchQueryReturn::Mapped => Ok(ListenCollection::new(
-                Listen::get_mapped_listen_of_user(conn, &self.user).await?,
-            )),
-            ListenFetchQueryReturn::Unmapped => Ok(ListenCollection::new(
-                Listen::get_unmapped_listen_of_user(conn, &self.user).await?,
-            )),
-            ListenFetchQueryReturn::None => Ok(ListenCollection::default()),
-        }
+        let listens = match self.returns {
+            ListenFetchQueryReturn::Mapped => {
+                Listen::get_mapped_listen_of_user(conn, &self.user).await?
+            }
+            ListenFetchQueryReturn::Unmapped => {
+                Listen::get_unmapped_listen_of_user(conn, &self.user).await?
+            }
+            ListenFetchQueryReturn::None => return Ok(ListenCollection::default()),
+        };
+
+        let min_listened_at = self
+            .min_listened_at
+            .map(|date| date.timestamp())
+            .unwrap_or(i64::MIN);
+        let max_listened_at = self
+            .max_listened_at
+            .map(|date| date.timestamp())
+            .unwrap_or(i64::MAX);
+
+        Ok(ListenCollection::new(
+            listens
+                .into_iter()
+                .filter(|listen| {
+                    min_listened_at <= listen.listened_at && listen.listened_at <= max_listened_at
+                })
+                .collect(),
+        ))
     }
 
     #[instrument(fields(indicatif.pb_show = tracing::field::Empty))]
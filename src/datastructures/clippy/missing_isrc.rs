@@ -1,14 +1,163 @@
+use itertools::Itertools as _;
 use musicbrainz_db_lite::models::musicbrainz::main_entities::MainEntity;
+use musicbrainz_db_lite::models::musicbrainz::release::Release;
+use musicbrainz_rs::entity::release::Release as MBRelease;
+use musicbrainz_rs::Fetch as _;
+use serde::Deserialize;
+use serde::Serialize;
 
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::api::musicbrainz::cache::fetch_cached;
+use crate::models::clippy::MbClippyLint;
+use crate::models::clippy::MbClippyLintHint;
+use crate::models::clippy::MbClippyLintLink;
+use crate::utils::cli::display::ReleaseExt as _;
 
-pub struct MissingIsrcLint {}
+/// The medium format of the releases sold on digital stores
+const DIGITAL_FORMAT: &str = "Digital Media";
 
-impl MissingIsrcLint {
-    pub fn check(conn: &mut sqlx::SqliteConnection, entity: MainEntity) -> Result<(), Self> {
-        let MainEntity::Recording(recording) = entity else {return Ok(())};
+/// A track on a digital medium of the release
+#[derive(Debug, Serialize, Deserialize)]
+struct DigitalTrack {
+    /// The MBID of the recording
+    mbid: String,
+    title: String,
+    has_isrcs: bool,
+}
 
-        recording.
+/// Get the tracks of the digital media. The ISRCs of a CD don't mean that the digital tracks have some, so other media are ignored
+fn get_digital_tracks(release: MBRelease) -> Vec<DigitalTrack> {
+    release
+        .media
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|media| media.format.as_deref() == Some(DIGITAL_FORMAT))
+        .flat_map(|media| media.tracks.unwrap_or_default())
+        .map(|track| track.recording)
+        .unique_by(|recording| recording.id.clone())
+        .map(|recording| DigitalTrack {
+            has_isrcs: recording
+                .isrcs
+                .as_ref()
+                .is_some_and(|isrcs| !isrcs.is_empty()),
+            mbid: recording.id,
+            title: recording.title,
+        })
+        .collect_vec()
+}
 
-        Ok(())
+pub struct MissingIsrcLint {
+    release: Release,
+
+    /// The MBIDs and titles of the recordings without ISRCs
+    recordings: Vec<(String, String)>,
+}
+
+impl MbClippyLint for MissingIsrcLint {
+    type Options = ();
+
+    fn get_name() -> &'static str {
+        "missing_isrc"
+    }
+
+    fn get_description() -> &'static str {
+        "Recordings on digital media without ISRC, while the other digital tracks of the release have one"
+    }
+
+    async fn check(
+        _conn: &mut sqlx::SqliteConnection,
+        entity: &MainEntity,
+        _options: &Self::Options,
+    ) -> Result<Option<Self>, crate::Error> {
+        let MainEntity::Release(release) = entity else {
+            return Ok(None);
+        };
+
+        // The local database doesn't have the medium formats nor the ISRCs, so get them from the API in a single request.
+        // Releases that never got fetched can't be checked offline
+        let fetch = async {
+            let mb_release = MBRelease::fetch()
+                .id(&release.mbid)
+                .with_recordings()
+                .with_isrcs()
+                .execute_with_client(&ALISTRAL_CLIENT.musicbrainz_rs)
+                .await?;
+
+            Ok::<_, crate::Error>(get_digital_tracks(mb_release))
+        };
+        let Some(digital_tracks) = fetch_cached("release", &release.mbid, fetch).await? else {
+            return Ok(None);
+        };
+
+        if !digital_tracks.iter().any(|track| track.has_isrcs) {
+            return Ok(None);
+        }
+
+        let recordings = digital_tracks
+            .into_iter()
+            .filter(|track| !track.has_isrcs)
+            .map(|track| (track.mbid, track.title))
+            .collect_vec();
+
+        if recordings.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            release: release.clone(),
+            recordings,
+        }))
+    }
+
+    async fn get_body(
+        &self,
+        conn: &mut sqlx::SqliteConnection,
+    ) -> Result<impl std::fmt::Display, crate::Error> {
+        let mut body = format!(
+            "Release \"{}\" has digital tracks without ISRCs, while other digital tracks have one
+-> Digital stores give an ISRC to each track. Check the ISRCs of the release, and add the missing ones:",
+            self.release.pretty_format_with_credits(conn, false).await?
+        );
+
+        for (_, title) in &self.recordings {
+            body.push_str(&format!("\n    - {title}"));
+        }
+
+        Ok(body)
+    }
+
+    async fn get_links(
+        &self,
+        _conn: &mut sqlx::SqliteConnection,
+    ) -> Result<Vec<MbClippyLintLink>, crate::Error> {
+        let mut out = vec![
+            MbClippyLintLink {
+                name: "Release".to_string(),
+                url: format!("https://musicbrainz.org/release/{}", self.release.mbid),
+            },
+            MbClippyLintLink {
+                name: "MagicISRC".to_string(),
+                url: format!("https://magicisrc.kepstin.ca/?mbid={}", self.release.mbid),
+            },
+        ];
+
+        for (mbid, title) in &self.recordings {
+            out.push(MbClippyLintLink {
+                name: format!("Recording \"{title}\""),
+                url: format!("https://musicbrainz.org/recording/{mbid}"),
+            });
+        }
+
+        Ok(out)
+    }
+
+    async fn get_hints(
+        &self,
+        _conn: &mut sqlx::SqliteConnection,
+    ) -> Result<Vec<MbClippyLintHint>, crate::Error> {
+        Ok(vec![MbClippyLintHint::new(
+            "Harmony can find the ISRCs of a release from the stores it's sold on, and MagicISRC can submit them"
+                .to_string(),
+        )])
     }
-}
\ No newline at end of file
+}
@@ -1,7 +1,7 @@
 use musicbrainz_db_lite::models::musicbrainz::main_entities::MainEntity;
 use musicbrainz_db_lite::models::musicbrainz::release::Release;
+use serde::Deserialize;
 
-use crate::models::clippy::lint_severity::LintSeverity;
 use crate::models::clippy::{MbClippyLint, MbClippyLintLink};
 use crate::utils::cli::display::ReleaseExt;
 
@@ -9,14 +9,28 @@ pub struct MissingBarcodeLint {
     release: Release,
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub struct MissingBarcodeOptions {
+    /// Only check the releases of those labels. All the releases are checked if empty
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
 impl MbClippyLint for MissingBarcodeLint {
+    type Options = MissingBarcodeOptions;
+
     fn get_name() -> &'static str {
         "missing_release_barcode"
     }
 
+    fn get_description() -> &'static str {
+        "Releases without a barcode, that aren't marked as having none"
+    }
+
     async fn check(
-        _conn: &mut sqlx::SqliteConnection,
+        conn: &mut sqlx::SqliteConnection,
         entity: &MainEntity,
+        options: &Self::Options,
     ) -> Result<Option<Self>, crate::Error> {
         let MainEntity::Release(release) = entity else {
             return Ok(None);
@@ -26,6 +40,18 @@ impl MbClippyLint for MissingBarcodeLint {
             return Ok(None);
         }
 
+        if !options.labels.is_empty() {
+            let label_infos = release.get_label_infos_or_fetch(conn).await?;
+
+            if !label_infos.iter().any(|info| {
+                info.label
+                    .as_ref()
+                    .is_some_and(|label| options.labels.contains(label))
+            }) {
+                return Ok(None);
+            }
+        }
+
         let missing_work_lint = Self {
             release: release.clone(),
         };
@@ -73,8 +99,4 @@ impl MbClippyLint for MissingBarcodeLint {
 
         Ok(hints)
     }
-
-    fn get_severity(&self) -> crate::models::clippy::lint_severity::LintSeverity {
-        LintSeverity::MissingData
-    }
 }
@@ -1,6 +1,5 @@
 use musicbrainz_db_lite::models::musicbrainz::{main_entities::MainEntity, recording::Recording};
 
-use crate::models::clippy::lint_severity::LintSeverity;
 use crate::models::clippy::{MbClippyLint, MbClippyLintLink};
 use crate::utils::cli::display::RecordingExt;
 use crate::utils::extensions::db_lite_ext::RelationRecordingArtistExt;
@@ -11,13 +10,20 @@ pub struct MissingRemixRelLint {
 }
 
 impl MbClippyLint for MissingRemixRelLint {
+    type Options = ();
+
     fn get_name() -> &'static str {
         "missing_remix_rel"
     }
 
+    fn get_description() -> &'static str {
+        "Recordings with a remixer relationship, but no `remix of` relationship to the original recording"
+    }
+
     async fn check(
         conn: &mut sqlx::SqliteConnection,
         entity: &MainEntity,
+        _options: &Self::Options,
     ) -> Result<Option<Self>, crate::Error> {
         let MainEntity::Recording(recording) = entity else {
             return Ok(None);
@@ -91,8 +97,4 @@ impl MbClippyLint for MissingRemixRelLint {
     ) -> Result<Vec<crate::models::clippy::MbClippyLintHint>, crate::Error> {
         Ok(Vec::new())
     }
-
-    fn get_severity(&self) -> crate::models::clippy::lint_severity::LintSeverity {
-        LintSeverity::MissingRelation
-    }
 }
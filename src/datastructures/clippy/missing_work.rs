@@ -1,7 +1,6 @@
 use musicbrainz_db_lite::models::musicbrainz::{main_entities::MainEntity, recording::Recording};
 
 use crate::api::clients::ALISTRAL_CLIENT;
-use crate::models::clippy::lint_severity::LintSeverity;
 use crate::models::clippy::MbClippyLintHint;
 use crate::models::clippy::{MbClippyLint, MbClippyLintLink};
 use crate::utils::cli::display::RecordingExt;
@@ -11,13 +10,20 @@ pub struct MissingWorkLint {
 }
 
 impl MbClippyLint for MissingWorkLint {
+    type Options = ();
+
     fn get_name() -> &'static str {
         "missing_recording_work"
     }
 
+    fn get_description() -> &'static str {
+        "Recordings without any associated work"
+    }
+
     async fn check(
         conn: &mut sqlx::SqliteConnection,
         entity: &MainEntity,
+        _options: &Self::Options,
     ) -> Result<Option<Self>, crate::Error> {
         let MainEntity::Recording(recording) = entity else {
             return Ok(None);
@@ -87,8 +93,4 @@ impl MbClippyLint for MissingWorkLint {
         // TODO: Remix hint
         Ok(hints)
     }
-
-    fn get_severity(&self) -> crate::models::clippy::lint_severity::LintSeverity {
-        LintSeverity::MissingData
-    }
 }
@@ -1,44 +1,111 @@
 use musicbrainz_db_lite::models::musicbrainz::main_entities::MainEntity;
 use musicbrainz_db_lite::models::musicbrainz::work::Work;
+use musicbrainz_rs::entity::work::Work as MBWork;
+use musicbrainz_rs::Fetch as _;
 
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::api::musicbrainz::cache::fetch_cached;
 use crate::models::clippy::MbClippyLint;
+use crate::models::clippy::MbClippyLintHint;
+use crate::models::clippy::MbClippyLintLink;
+use crate::utils::cli::display::WorkExt as _;
+
+/// The artist relationship types showing that a work has lyrics
+const LYRICS_RELATIONS: [&str; 2] = ["lyricist", "librettist"];
 
 pub struct MissingWorkLanguageLint {
-    work: Work
+    work: Work,
 }
 
 impl MbClippyLint for MissingWorkLanguageLint {
+    type Options = ();
+
+    fn get_name() -> &'static str {
+        "missing_work_language"
+    }
+
+    fn get_description() -> &'static str {
+        "Works with lyricist or librettist relationships, but no lyrics language"
+    }
+
     async fn check(
         conn: &mut sqlx::SqliteConnection,
-        entity: &musicbrainz_db_lite::models::musicbrainz::main_entities::MainEntity,
+        entity: &MainEntity,
+        _options: &Self::Options,
     ) -> Result<Option<Self>, crate::Error> {
         let MainEntity::Work(work) = entity else {
             return Ok(None);
         };
-    }
 
-    fn get_name() -> &'static str {
-        todo!()
+        let has_lyrics = work
+            .get_artist_relations(conn)
+            .await?
+            .iter()
+            .any(|relation| LYRICS_RELATIONS.contains(&relation.relation_type.as_str()));
+
+        if !has_lyrics {
+            return Ok(None);
+        }
+
+        // The local database doesn't have the work languages, so only the works with lyrics get fetched.
+        // Works that never got fetched can't be checked offline
+        let fetch = async {
+            let mb_work = MBWork::fetch()
+                .id(&work.mbid)
+                .execute_with_client(&ALISTRAL_CLIENT.musicbrainz_rs)
+                .await?;
+
+            Ok::<_, crate::Error>(
+                mb_work.language.is_some()
+                    || mb_work
+                        .languages
+                        .is_some_and(|languages| !languages.is_empty()),
+            )
+        };
+        let Some(has_language) = fetch_cached("work", &work.mbid, fetch).await? else {
+            return Ok(None);
+        };
+
+        if has_language {
+            return Ok(None);
+        }
+
+        Ok(Some(Self { work: work.clone() }))
     }
 
     async fn get_body(
         &self,
-        conn: &mut sqlx::SqliteConnection,
+        _conn: &mut sqlx::SqliteConnection,
     ) -> Result<impl std::fmt::Display, crate::Error> {
-        todo!()
+        Ok(format!(
+            "Work \"{}\" has lyrics relationships, but no lyrics language
+-> Set the language the lyrics are written in",
+            self.work.pretty_format().await?
+        ))
     }
 
     async fn get_links(
         &self,
-        conn: &mut sqlx::SqliteConnection,
-    ) -> Result<Vec<crate::models::clippy::MbClippyLintLink>, crate::Error> {
-        todo!()
+        _conn: &mut sqlx::SqliteConnection,
+    ) -> Result<Vec<MbClippyLintLink>, crate::Error> {
+        Ok(vec![
+            MbClippyLintLink {
+                name: "Work".to_string(),
+                url: format!("https://musicbrainz.org/work/{}", self.work.mbid),
+            },
+            MbClippyLintLink {
+                name: "Work editing".to_string(),
+                url: format!("https://musicbrainz.org/work/{}/edit", self.work.mbid),
+            },
+        ])
     }
 
     async fn get_hints(
         &self,
-        conn: &mut sqlx::SqliteConnection,
-    ) -> Result<Vec<crate::models::clippy::MbClippyLintHint>, crate::Error> {
-        todo!()
+        _conn: &mut sqlx::SqliteConnection,
+    ) -> Result<Vec<MbClippyLintHint>, crate::Error> {
+        Ok(vec![MbClippyLintHint::new(
+            "Use `[Multiple languages]` if the lyrics mix several languages, and `[No lyrics]` if the relationships are wrong and the work is instrumental".to_string(),
+        )])
     }
-}
\ No newline at end of file
+}
@@ -1,8 +1,9 @@
+pub mod missing_isrc;
+pub mod missing_release_barcode;
 pub mod missing_remix_rel;
 pub mod missing_remixer_rel;
+pub mod missing_work;
+pub mod missing_work_language;
+pub mod registry;
 pub mod soundtrack_without_disambiguation;
 pub mod suspicious_remix;
-//pub mod missing_work_language; // Need work languages
-pub mod missing_release_barcode;
-pub mod missing_work;
-//pub mod missing_isrc;
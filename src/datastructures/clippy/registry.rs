@@ -0,0 +1,135 @@
+use core::future::Future;
+use std::pin::Pin;
+
+use musicbrainz_db_lite::models::musicbrainz::main_entities::MainEntity;
+
+use crate::datastructures::clippy::missing_isrc::MissingIsrcLint;
+use crate::datastructures::clippy::missing_release_barcode::MissingBarcodeLint;
+use crate::datastructures::clippy::missing_remix_rel::MissingRemixRelLint;
+use crate::datastructures::clippy::missing_remixer_rel::MissingRemixerRelLint;
+use crate::datastructures::clippy::missing_work::MissingWorkLint;
+use crate::datastructures::clippy::missing_work_language::MissingWorkLanguageLint;
+use crate::datastructures::clippy::soundtrack_without_disambiguation::SoundtrackWithoutDisambiguationLint;
+use crate::datastructures::clippy::suspicious_remix::SuspiciousRemixLint;
+use crate::models::clippy::lint_severity::LintSeverity;
+use crate::models::clippy::report::ClippyReport;
+use crate::models::clippy::MbClippyLint;
+use crate::models::config::clippy::ClippyConfig;
+use crate::models::config::config_guard::ConfigGuard;
+use crate::models::config::dismissed_clippy_lints::DismissedClippyLints;
+use crate::tools::musicbrainz::clippy::check_lint;
+
+/// A lint that clippy can check. New lints need to be added to [`ClippyLintKind::ALL`] to be checked
+#[derive(Debug, Clone, Copy)]
+pub struct ClippyLintKind {
+    name: fn() -> &'static str,
+    description: fn() -> &'static str,
+    default_severity: LintSeverity,
+
+    /// Read the options of the lint from the config, and get a checker using them
+    constructor:
+        fn(&ClippyConfig, LintSeverity) -> Result<Box<dyn ClippyLintChecker>, crate::Error>,
+}
+
+impl ClippyLintKind {
+    /// All the lints, in the order they are checked
+    pub const ALL: [Self; 8] = [
+        Self::new::<MissingWorkLint>(LintSeverity::MissingData),
+        Self::new::<MissingBarcodeLint>(LintSeverity::MissingData),
+        Self::new::<SuspiciousRemixLint>(LintSeverity::MissingRelation),
+        Self::new::<MissingRemixRelLint>(LintSeverity::MissingRelation),
+        Self::new::<MissingRemixerRelLint>(LintSeverity::MissingRelation),
+        Self::new::<SoundtrackWithoutDisambiguationLint>(LintSeverity::StyleIssue),
+        Self::new::<MissingIsrcLint>(LintSeverity::MissingData),
+        Self::new::<MissingWorkLanguageLint>(LintSeverity::MissingData),
+    ];
+
+    const fn new<L: MbClippyLint + 'static>(default_severity: LintSeverity) -> Self {
+        Self {
+            name: L::get_name,
+            description: L::get_description,
+            default_severity,
+            constructor: ConfiguredLint::<L>::new_boxed,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        (self.name)()
+    }
+
+    pub fn get_description(&self) -> &'static str {
+        (self.description)()
+    }
+
+    pub fn get_default_severity(&self) -> LintSeverity {
+        self.default_severity
+    }
+
+    /// Find a lint by its name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|lint| lint.get_name() == name)
+    }
+
+    /// Get a checker for the lint, with its options and severity read from the config.
+    ///
+    /// This fails if the options of the lint are invalid, so it should be called before starting to check
+    pub fn get_checker(
+        &self,
+        config: &ClippyConfig,
+    ) -> Result<Box<dyn ClippyLintChecker>, crate::Error> {
+        let severity = config
+            .get_severity_override(self.get_name())
+            .unwrap_or(self.default_severity);
+
+        (self.constructor)(config, severity)
+    }
+}
+
+/// A lint ready to be checked on entities
+pub trait ClippyLintChecker {
+    /// Check the lint on the entity. Returns `false` if the user wants to stop
+    fn check<'a>(
+        &'a self,
+        conn: &'a mut sqlx::SqliteConnection,
+        entity: &'a mut MainEntity,
+        dismissed: &'a ConfigGuard<DismissedClippyLints>,
+        report: Option<&'a mut ClippyReport>,
+    ) -> Pin<Box<dyn Future<Output = bool> + 'a>>;
+}
+
+/// A lint with its options and severity read from the config
+struct ConfiguredLint<L: MbClippyLint> {
+    options: L::Options,
+    severity: LintSeverity,
+}
+
+impl<L: MbClippyLint + 'static> ConfiguredLint<L> {
+    fn new_boxed(
+        config: &ClippyConfig,
+        severity: LintSeverity,
+    ) -> Result<Box<dyn ClippyLintChecker>, crate::Error> {
+        Ok(Box::new(Self {
+            options: config.get_options::<L::Options>(L::get_name())?,
+            severity,
+        }))
+    }
+}
+
+impl<L: MbClippyLint + 'static> ClippyLintChecker for ConfiguredLint<L> {
+    fn check<'a>(
+        &'a self,
+        conn: &'a mut sqlx::SqliteConnection,
+        entity: &'a mut MainEntity,
+        dismissed: &'a ConfigGuard<DismissedClippyLints>,
+        report: Option<&'a mut ClippyReport>,
+    ) -> Pin<Box<dyn Future<Output = bool> + 'a>> {
+        Box::pin(check_lint::<L>(
+            conn,
+            entity,
+            &self.options,
+            self.severity,
+            dismissed,
+            report,
+        ))
+    }
+}
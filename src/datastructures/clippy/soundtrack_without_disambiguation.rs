@@ -2,7 +2,6 @@ use color_eyre::owo_colors::OwoColorize;
 use musicbrainz_db_lite::models::musicbrainz::main_entities::MainEntity;
 use musicbrainz_db_lite::models::musicbrainz::work::Work;
 
-use crate::models::clippy::lint_severity::LintSeverity;
 use crate::models::clippy::MbClippyLint;
 use crate::models::clippy::MbClippyLintLink;
 use crate::utils::cli::display::WorkExt as _;
@@ -12,13 +11,20 @@ pub struct SoundtrackWithoutDisambiguationLint {
 }
 
 impl MbClippyLint for SoundtrackWithoutDisambiguationLint {
+    type Options = ();
+
     fn get_name() -> &'static str {
         "soundtrack_without_disambiguation"
     }
 
+    fn get_description() -> &'static str {
+        "Soundtrack works without a disambiguation naming the original work"
+    }
+
     async fn check(
         _conn: &mut sqlx::SqliteConnection,
         entity: &musicbrainz_db_lite::models::musicbrainz::main_entities::MainEntity,
+        _options: &Self::Options,
     ) -> Result<Option<Self>, crate::Error> {
         let MainEntity::Work(work) = entity else {
             return Ok(None);
@@ -66,8 +72,4 @@ impl MbClippyLint for SoundtrackWithoutDisambiguationLint {
             },
         ])
     }
-
-    fn get_severity(&self) -> crate::models::clippy::lint_severity::LintSeverity {
-        LintSeverity::StyleIssue
-    }
 }
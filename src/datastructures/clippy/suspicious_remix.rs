@@ -2,7 +2,6 @@ use musicbrainz_db_lite::models::musicbrainz::main_entities::MainEntity;
 use musicbrainz_db_lite::models::musicbrainz::recording::Recording;
 use regex::Regex;
 
-use crate::models::clippy::lint_severity::LintSeverity;
 use crate::models::clippy::MbClippyLint;
 use crate::models::clippy::MbClippyLintLink;
 use crate::utils::cli::display::RecordingExt as _;
@@ -13,13 +12,20 @@ pub struct SuspiciousRemixLint {
 }
 
 impl MbClippyLint for SuspiciousRemixLint {
+    type Options = ();
+
     fn get_name() -> &'static str {
         "suspicious_remix"
     }
 
+    fn get_description() -> &'static str {
+        "Recordings with a remix or VIP title, but no remix relationships"
+    }
+
     async fn check(
         conn: &mut sqlx::SqliteConnection,
         entity: &musicbrainz_db_lite::models::musicbrainz::main_entities::MainEntity,
+        _options: &Self::Options,
     ) -> Result<Option<Self>, crate::Error> {
         let MainEntity::Recording(recording) = entity else {
             return Ok(None);
@@ -83,8 +89,4 @@ impl MbClippyLint for SuspiciousRemixLint {
     ) -> Result<Vec<crate::models::clippy::MbClippyLintHint>, crate::Error> {
         Ok(Vec::new())
     }
-
-    fn get_severity(&self) -> crate::models::clippy::lint_severity::LintSeverity {
-        LintSeverity::MissingRelation
-    }
 }
@@ -19,13 +19,15 @@ impl RecordingWithListens {
     pub async fn get_lookup_report(
         &self,
         conn: &mut sqlx::SqliteConnection,
+        username: &str,
         other_listens: &RecordingWithListensCollection,
     ) -> Result<String, crate::Error> {
         if self.is_empty() {
             return self.generate_empty_report(conn).await;
         }
 
-        self.generate_full_report(conn, other_listens).await
+        self.generate_full_report(conn, username, other_listens)
+            .await
     }
 
     async fn generate_empty_report(
@@ -43,6 +45,7 @@ impl RecordingWithListens {
     async fn generate_full_report(
         &self,
         conn: &mut sqlx::SqliteConnection,
+        username: &str,
         other_listens: &RecordingWithListensCollection,
     ) -> Result<String, crate::Error> {
         let conf = Config::load_or_panic();
@@ -90,7 +93,7 @@ impl RecordingWithListens {
             underrated_score = self.get_underated_score(other_listens, global_count).trunc_with_scale(2),
             overdue_score = self.overdue_factor().trunc_with_scale(2)  + Decimal::ONE,
             overdue_mul = ((self.overdue_factor() + Decimal::ONE)
-            * conf.read_or_panic().bumps.get_multiplier(&self.recording().mbid.clone()))
+            * conf.read_or_panic().bumps.get_multiplier(username, &self.recording().mbid.clone()))
         .trunc_with_scale(2)
         };
 
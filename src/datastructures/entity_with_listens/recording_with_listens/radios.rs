@@ -1,3 +1,4 @@
+use alistral_core::datastructures::entity_with_listens::collection::rank_ratio;
 use alistral_core::datastructures::listen_collection::traits::ListenCollectionReadable as _;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
@@ -16,13 +17,12 @@ impl RecordingWithListens {
     }
 
     fn get_underrated_rank_score(&self, user_listens: &RecordingWithListensCollection) -> Decimal {
-        // Retrive the rank of the recording in the user listens
-        let rank = user_listens.get_rank(&self.recording.mbid).unwrap_or(9999);
-
-        // The recording vec scores if its between 0 and 999.
-        Decimal::from(1000_u64.saturating_sub(rank.try_into().unwrap()))
-            .checked_div(dec!(10))
-            .unwrap_or(Decimal::ZERO)
+        // x100 to get the score to max out at 100 points
+        rank_ratio(
+            user_listens.get_rank(&self.recording.mbid),
+            user_listens.len(),
+        )
+        .saturating_mul(dec!(100))
     }
 
     fn get_underrated_listen_score(
@@ -1,46 +1,150 @@
+use std::path::PathBuf;
+
 use chrono::Duration;
 use futures::Stream;
 use futures::StreamExt;
+use itertools::Itertools as _;
 use macon::Builder;
 use musicbrainz_db_lite::models::musicbrainz::recording::Recording;
+use musicbrainz_db_lite::models::musicbrainz::release::Release;
+use tracing::debug;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::database::listenbrainz::prefetching::prefetch_releases;
+use crate::datastructures::radio::sorters::transitions::TransitionSorter;
+use crate::models::cli::radio::parse_duration;
+
+/// The playlist is full once the time left before the maximum duration is shorter than this
+const MAX_DURATION_TOLERANCE_SECS: i64 = 120;
+
+/// The playlist is full once this many tracks in a row are too long for the time left
+const MAX_DURATION_MISSES: u32 = 25;
 
 #[derive(Debug, Builder)]
 pub struct RadioCollector {
     duration: Option<Duration>,
     count: Option<u64>,
+
+    /// The maximum number of tracks of the same artist
+    max_per_artist: Option<u64>,
+
+    /// The maximum number of tracks of the same release group
+    max_per_release_group: Option<u64>,
+
+    /// The minimum number of tracks between two tracks of the same artist
+    artist_spacing: Option<u64>,
+
+    /// The maximum duration of the playlist
+    max_duration: Option<Duration>,
+
+    /// Reorder the collected playlist for smooth transitions
+    transitions: Option<TransitionSorter>,
+}
+
+/// The user given options of a [`RadioCollector`], as found in the CLI arguments and the daemon config
+#[derive(Debug, Clone, Default)]
+pub struct RadioCollectorOptions {
+    pub min_count: Option<u64>,
+
+    /// The minimum duration, in natural language
+    pub min_duration: Option<String>,
+
+    /// The maximum duration, in natural language
+    pub max_duration: Option<String>,
+
+    pub max_per_artist: Option<u64>,
+    pub max_per_release_group: Option<u64>,
+    pub artist_spacing: Option<u64>,
+    pub smooth_transitions: bool,
+    pub transition_features: Option<PathBuf>,
+}
+
+impl RadioCollectorOptions {
+    pub fn build(&self) -> Result<RadioCollector, crate::Error> {
+        let collector = RadioCollectorBuilder::default();
+
+        let collector = match self.min_count {
+            Some(val) => collector.count(val),
+            None => collector.count_none(),
+        };
+
+        let collector = match self.min_duration.as_ref() {
+            Some(val) => collector.duration(parse_duration(val)?),
+            None => collector.duration_none(),
+        };
+
+        let collector = match self.max_duration.as_ref() {
+            Some(val) => collector.max_duration(parse_duration(val)?),
+            None => collector.max_duration_none(),
+        };
+
+        let collector = match self.max_per_artist {
+            Some(val) => collector.max_per_artist(val),
+            None => collector.max_per_artist_none(),
+        };
+
+        let collector = match self.max_per_release_group {
+            Some(val) => collector.max_per_release_group(val),
+            None => collector.max_per_release_group_none(),
+        };
+
+        let collector = match self.artist_spacing {
+            Some(val) => collector.artist_spacing(val),
+            None => collector.artist_spacing_none(),
+        };
+
+        let collector = if self.smooth_transitions {
+            collector.transitions(TransitionSorter {
+                features_file: self.transition_features.clone(),
+            })
+        } else {
+            collector.transitions_none()
+        };
+
+        Ok(collector.build())
+    }
 }
 
 impl RadioCollector {
     pub async fn collect(
         &self,
+        conn: &mut sqlx::SqliteConnection,
         recordings: impl Stream<Item = Recording> + Unpin,
-    ) -> Vec<Recording> {
-        self.try_collect(recordings.map(Ok))
-            .await
-            .expect("All the items are `Ok`, so no potential error")
+    ) -> Result<Vec<Recording>, crate::Error> {
+        self.try_collect(conn, recordings.map(Ok)).await
     }
 
     pub async fn try_collect(
         &self,
+        conn: &mut sqlx::SqliteConnection,
         mut recordings: impl Stream<Item = Result<Recording, crate::Error>> + Unpin,
     ) -> Result<Vec<Recording>, crate::Error> {
-        let mut results = Vec::new();
-        while let Some(recording) = recordings.next().await.transpose()? {
-            results.push(recording.clone());
+        let mut playlist = self.new_playlist();
 
-            if self.check_minimum_lenght(&results) {
-                return Ok(results);
+        while let Some(recording) = recordings.next().await.transpose()? {
+            if playlist.push(conn, recording).await? {
+                break;
             }
         }
 
-        Ok(results)
+        playlist.finish_with_transitions(conn).await
+    }
+
+    /// Start a new playlist to push recordings into. This is useful for radios that can't give their recordings as a stream
+    pub fn new_playlist(&self) -> CollectedPlaylist<'_> {
+        CollectedPlaylist {
+            collector: self,
+            tracks: Vec::new(),
+            deferred: Vec::new(),
+            duration_misses: 0,
+        }
     }
 
     /// Return true if the lenght of the playlist satisfy the requested minimum time
-    pub fn check_minimum_lenght(&self, playlist: &[Recording]) -> bool {
+    fn check_minimum_lenght(&self, count: usize, duration: Duration) -> bool {
         let has_min_count = match self.count {
-            Some(count) => playlist.len() as u64 >= count,
-            None => self.duration.is_some() || playlist.len() as u64 >= 50,
+            Some(min_count) => count as u64 >= min_count,
+            None => self.duration.is_some() || count as u64 >= 50,
         };
 
         if !has_min_count {
@@ -48,14 +152,302 @@ impl RadioCollector {
         }
 
         match self.duration {
-            Some(duration) => {
-                playlist
-                    .iter()
-                    .map(|recording| recording.length_as_duration().unwrap_or_default())
-                    .sum::<Duration>()
-                    >= duration
-            }
+            Some(min_duration) => duration >= min_duration,
             None => true,
         }
     }
+
+    fn has_artist_constraints(&self) -> bool {
+        self.max_per_artist.is_some() || self.artist_spacing.is_some()
+    }
+}
+
+/// A playlist being filled by a [`RadioCollector`]
+pub struct CollectedPlaylist<'a> {
+    collector: &'a RadioCollector,
+    tracks: Vec<CollectedTrack>,
+
+    /// The tracks that break the artist spacing, waiting for a spot in the playlist
+    deferred: Vec<CollectedTrack>,
+
+    /// The number of tracks in a row that were too long for the maximum duration
+    duration_misses: u32,
+}
+
+impl CollectedPlaylist<'_> {
+    /// Add a recording to the playlist, if it respects the constraints of the collector.
+    ///
+    /// Returns true once the playlist is complete
+    pub async fn push(
+        &mut self,
+        conn: &mut sqlx::SqliteConnection,
+        recording: Recording,
+    ) -> Result<bool, crate::Error> {
+        let track = CollectedTrack::new(conn, self.collector, recording).await?;
+
+        // Some deferred tracks may now be spaced enough
+        self.place_deferred();
+        if self.is_complete() {
+            return Ok(true);
+        }
+
+        self.count_duration_miss(self.fits_duration(&track.recording));
+
+        match self.check(&track) {
+            Placement::Accept => self.tracks.push(track),
+            Placement::Defer => {
+                debug!(
+                    "Deferring `{}`: too close to the same artist",
+                    track.recording.title
+                );
+                self.deferred.push(track);
+            }
+            Placement::Skip => {
+                debug!(
+                    "Skipping `{}`: it breaks the radio's constraints",
+                    track.recording.title
+                );
+            }
+        }
+
+        Ok(self.is_complete())
+    }
+
+    /// Add all the tracks of an album to the playlist, in order.
+    ///
+    /// Albums are kept whole, so the playlist may go over its minimum length. Only the maximum duration is checked,
+    /// as the other constraints would cut the album.
+    ///
+    /// Returns true once the playlist is complete
+    pub fn push_album(&mut self, recordings: Vec<Recording>) -> bool {
+        if self.is_complete() {
+            return true;
+        }
+
+        if let Some(max) = self.collector.max_duration {
+            let fits = self.get_duration() + get_total_duration(recordings.iter()) <= max;
+            self.count_duration_miss(fits);
+
+            if !fits {
+                debug!("Skipping album: it is too long for the playlist");
+                return self.is_complete();
+            }
+        }
+
+        self.tracks
+            .extend(recordings.into_iter().map(|recording| CollectedTrack {
+                recording,
+                artists: Vec::new(),
+                release_groups: Vec::new(),
+            }));
+
+        self.is_complete()
+    }
+
+    /// Get the recordings of the playlist
+    pub fn finish(mut self) -> Vec<Recording> {
+        self.place_deferred();
+
+        // The source is exhausted, so the deferred tracks are better than an incomplete playlist
+        if !self.is_complete() {
+            for track in std::mem::take(&mut self.deferred) {
+                if self.is_complete() {
+                    break;
+                }
+
+                if self.check(&track) != Placement::Skip {
+                    self.tracks.push(track);
+                }
+            }
+        }
+
+        self.tracks.into_iter().map(|t| t.recording).collect_vec()
+    }
+
+    /// Get the recordings of the playlist, reordered for smooth transitions if the collector requires it
+    pub async fn finish_with_transitions(
+        self,
+        conn: &mut sqlx::SqliteConnection,
+    ) -> Result<Vec<Recording>, crate::Error> {
+        let transitions = self.collector.transitions.clone();
+        let recordings = self.finish();
+
+        match transitions {
+            Some(sorter) => sorter.sort(conn, recordings).await,
+            None => Ok(recordings),
+        }
+    }
+
+    fn place_deferred(&mut self) {
+        while let Some(pos) = self
+            .deferred
+            .iter()
+            .position(|track| self.check(track) == Placement::Accept)
+        {
+            if self.is_complete() {
+                return;
+            }
+
+            let track = self.deferred.remove(pos);
+            self.tracks.push(track);
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        let duration = self.get_duration();
+
+        if self
+            .collector
+            .check_minimum_lenght(self.tracks.len(), duration)
+        {
+            return true;
+        }
+
+        // Exact fits are rare, so stop once the time left is small, or nothing seems to fit anymore
+        self.collector.max_duration.is_some_and(|max| {
+            duration + Duration::seconds(MAX_DURATION_TOLERANCE_SECS) >= max
+                || self.duration_misses >= MAX_DURATION_MISSES
+        })
+    }
+
+    fn count_duration_miss(&mut self, fits: bool) {
+        if fits {
+            self.duration_misses = 0;
+        } else {
+            self.duration_misses += 1;
+        }
+    }
+
+    /// Return true if the recording can be added without going over the maximum duration
+    fn fits_duration(&self, recording: &Recording) -> bool {
+        self.collector.max_duration.is_none_or(|max| {
+            self.get_duration() + recording.length_as_duration().unwrap_or_default() <= max
+        })
+    }
+
+    fn get_duration(&self) -> Duration {
+        get_total_duration(self.tracks.iter().map(|t| &t.recording))
+    }
+
+    fn check(&self, track: &CollectedTrack) -> Placement {
+        if !self.fits_duration(&track.recording) {
+            return Placement::Skip;
+        }
+
+        if let Some(max) = self.collector.max_per_artist {
+            let count = self
+                .tracks
+                .iter()
+                .filter(|t| t.shares_artist(track))
+                .count();
+
+            if count as u64 >= max {
+                return Placement::Skip;
+            }
+        }
+
+        if let Some(max) = self.collector.max_per_release_group {
+            let count = self
+                .tracks
+                .iter()
+                .filter(|t| t.shares_release_group(track))
+                .count();
+
+            if count as u64 >= max {
+                return Placement::Skip;
+            }
+        }
+
+        if let Some(spacing) = self.collector.artist_spacing {
+            let too_close = self
+                .tracks
+                .iter()
+                .rev()
+                .take(spacing as usize)
+                .any(|t| t.shares_artist(track));
+
+            if too_close {
+                return Placement::Defer;
+            }
+        }
+
+        Placement::Accept
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placement {
+    Accept,
+
+    /// The track cannot be added now, but may be later
+    Defer,
+
+    /// The track cannot be added at all
+    Skip,
+}
+
+/// A recording with the data needed to check the collector's constraints
+struct CollectedTrack {
+    recording: Recording,
+    artists: Vec<String>,
+    release_groups: Vec<String>,
+}
+
+impl CollectedTrack {
+    async fn new(
+        conn: &mut sqlx::SqliteConnection,
+        collector: &RadioCollector,
+        recording: Recording,
+    ) -> Result<Self, crate::Error> {
+        let artists = if collector.has_artist_constraints() {
+            recording
+                .get_artists_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db)
+                .await?
+                .into_iter()
+                .map(|artist| artist.mbid)
+                .collect_vec()
+        } else {
+            Vec::new()
+        };
+
+        let release_groups = if collector.max_per_release_group.is_some() {
+            let releases = recording
+                .get_releases_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db)
+                .await?;
+            let release_refs = releases.iter().collect_vec();
+            prefetch_releases(conn, &release_refs).await?;
+
+            Release::get_release_groups_as_batch(conn, &release_refs)
+                .await?
+                .into_iter()
+                .flat_map(|(_, (_, groups))| groups)
+                .map(|group| group.mbid)
+                .unique()
+                .collect_vec()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            recording,
+            artists,
+            release_groups,
+        })
+    }
+
+    fn shares_artist(&self, other: &Self) -> bool {
+        self.artists.iter().any(|a| other.artists.contains(a))
+    }
+
+    fn shares_release_group(&self, other: &Self) -> bool {
+        self.release_groups
+            .iter()
+            .any(|rg| other.release_groups.contains(rg))
+    }
+}
+
+fn get_total_duration<'a>(playlist: impl Iterator<Item = &'a Recording>) -> Duration {
+    playlist
+        .map(|recording| recording.length_as_duration().unwrap_or_default())
+        .sum::<Duration>()
 }
@@ -0,0 +1,283 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::Utc;
+use interzic::models::playlist_history::PlaylistHistory;
+use interzic::models::playlist_stub::PlaylistStub;
+use interzic::models::services::jspf::Jspf;
+use interzic::models::services::listenbrainz::Listenbrainz;
+use interzic::models::services::local_files::LocalFiles;
+use interzic::models::services::local_files::LocalPlaylistFormat;
+use interzic::models::services::youtube::Youtube;
+use macon::Builder;
+use tracing::warn;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::datastructures::radio::report::RadioReport;
+use crate::models::cli::radio::RadioExportTarget;
+use crate::models::config::Config;
+use crate::models::data_storage::DataStorage;
+use crate::models::error::ResultTEExt as _;
+use crate::utils::cli::read_mbid_from_input;
+use crate::utils::data_file::DataFile as _;
+
+/// Send the generated radio playlist to its destination
+#[derive(Debug, Builder, Clone)]
+pub struct RadioExporter {
+    #[builder(Default=!)]
+    target: RadioExportTarget,
+
+    /// The file to write the playlist to, for file based targets
+    output_file: Option<PathBuf>,
+
+    /// The type of radio that generated the playlist. If set, the playlist is saved in the radio history
+    radio: Option<String>,
+
+    /// The parameters of the radio, as JSON
+    parameters: Option<String>,
+
+    /// The id of an existing playlist to replace, instead of creating a new one
+    replace: Option<String>,
+
+    /// The name of the playlist. If a playlist with this name already exists, it gets replaced
+    playlist_name: Option<String>,
+
+    /// Print the playlist instead of exporting it
+    dry_run: bool,
+
+    /// A recording to explain the presence or absence in the playlist
+    explain: Option<String>,
+
+    /// The steps the radio took to generate the playlist
+    report: Option<RadioReport>,
+}
+
+impl RadioExporter {
+    pub async fn export(
+        &self,
+        mut playlist: PlaylistStub,
+        username: Option<String>,
+        token: Option<&str>,
+    ) -> Result<(), crate::Error> {
+        if let Some(name) = &self.playlist_name {
+            playlist.title = name.clone();
+        }
+
+        let report = self.report.clone().unwrap_or_default();
+
+        if self.dry_run {
+            println!("{}", playlist.title);
+            report.print_tracks(&playlist);
+        }
+
+        if let Some(recording) = &self.explain {
+            report.explain(recording, &playlist).await?;
+        }
+
+        if self.dry_run {
+            println!("Dry run: the playlist hasn't been exported");
+            return Ok(());
+        }
+
+        self.send(playlist.clone(), username.clone(), token).await?;
+        self.save_history(playlist, username).await
+    }
+
+    async fn send(
+        &self,
+        playlist: PlaylistStub,
+        username: Option<String>,
+        token: Option<&str>,
+    ) -> Result<(), crate::Error> {
+        match self.target {
+            RadioExportTarget::Listenbrainz => {
+                let username = username.ok_or(crate::Error::MissingPlaylistUserDataError(
+                    "username".to_string(),
+                ))?;
+                let token = token.ok_or(crate::Error::MissingPlaylistUserDataError(
+                    "token".to_string(),
+                ))?;
+
+                let existing = match &self.replace {
+                    Some(id) => Some(read_mbid_from_input(id).unwrap_or_else(|| id.clone())),
+                    None => match &self.playlist_name {
+                        Some(name) => {
+                            Listenbrainz::find_playlist_by_name(
+                                &ALISTRAL_CLIENT.interzic,
+                                &username,
+                                name,
+                                token,
+                            )
+                            .await?
+                        }
+                        None => None,
+                    },
+                };
+
+                match existing {
+                    Some(id) => {
+                        let id = Listenbrainz::replace_playlist(
+                            &ALISTRAL_CLIENT.interzic,
+                            &id,
+                            playlist,
+                            username,
+                            token,
+                        )
+                        .await?;
+                        println!("Updated the playlist https://listenbrainz.org/playlist/{id}");
+                    }
+                    None => {
+                        Listenbrainz::create_playlist(
+                            &ALISTRAL_CLIENT.interzic,
+                            playlist,
+                            username,
+                            token,
+                        )
+                        .await?;
+                    }
+                }
+            }
+            RadioExportTarget::Youtube => {
+                let existing = match &self.replace {
+                    Some(id) => Some(get_youtube_playlist_id(id)),
+                    None => match &self.playlist_name {
+                        Some(name) => {
+                            Youtube::find_playlist_by_name(&ALISTRAL_CLIENT.interzic, name).await?
+                        }
+                        None => None,
+                    },
+                };
+
+                match existing {
+                    Some(id) => {
+                        let id = Youtube::replace_playlist(&ALISTRAL_CLIENT.interzic, id, playlist)
+                            .await?;
+                        println!("Updated the playlist https://www.youtube.com/playlist?list={id}");
+                    }
+                    None => {
+                        let _playlist_id =
+                            Youtube::create_playlist(&ALISTRAL_CLIENT.interzic, playlist).await?;
+                        //TODO: display url after export
+                    }
+                }
+            }
+            RadioExportTarget::Jspf => {
+                let path = self.get_output_file()?;
+
+                Jspf::save_playlist(playlist, username, path)?;
+                println!("Saved the playlist to {}", path.display());
+            }
+            RadioExportTarget::M3u8 => {
+                self.export_local(playlist, LocalPlaylistFormat::M3u8)
+                    .await?;
+            }
+            RadioExportTarget::Xspf => {
+                self.export_local(playlist, LocalPlaylistFormat::Xspf)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Export the playlist as a playlist file of the user's local music files
+    async fn export_local(
+        &self,
+        playlist: PlaylistStub,
+        format: LocalPlaylistFormat,
+    ) -> Result<(), crate::Error> {
+        let path = self.get_output_file()?;
+        let music_directory = Config::load_or_panic()
+            .read_or_panic()
+            .music_directory
+            .clone()
+            .ok_or(crate::Error::MissingMusicDirectoryError)?;
+
+        LocalFiles::index_directory(&ALISTRAL_CLIENT.interzic, &music_directory).await?;
+        let missing =
+            LocalFiles::save_playlist(&ALISTRAL_CLIENT.interzic, playlist, format, path).await?;
+
+        if !missing.is_empty() {
+            warn!(
+                "{} recordings have no local file, and have been left out of the playlist:",
+                missing.len()
+            );
+
+            for recording in missing {
+                warn!("    - {recording}");
+            }
+        }
+
+        println!("Saved the playlist to {}", path.display());
+        Ok(())
+    }
+
+    /// Get the number of the playlist, for its title. Dry runs don't count as a new playlist
+    pub fn get_playlist_number(&self) -> u64 {
+        let counter = DataStorage::load().expect_fatal("Couldn't load data storage");
+
+        if self.dry_run {
+            return counter.read_or_panic().get_playlist_count();
+        }
+
+        counter.write().unwrap().incr_playlist_count()
+    }
+
+    /// Return true if the radio should keep a report of its steps
+    pub fn needs_report(&self) -> bool {
+        self.dry_run || self.explain.is_some()
+    }
+
+    pub fn set_report(&mut self, report: RadioReport) {
+        self.report = Some(report);
+    }
+
+    /// Save the playlist in the radio history
+    async fn save_history(
+        &self,
+        playlist: PlaylistStub,
+        username: Option<String>,
+    ) -> Result<(), crate::Error> {
+        let Some(radio) = &self.radio else {
+            return Ok(());
+        };
+
+        let history = PlaylistHistory {
+            id: 0,
+            title: playlist.title,
+            description: playlist.description,
+            kind: radio.clone(),
+            parameters: self.parameters.clone().unwrap_or_else(|| "{}".to_string()),
+            target: self.target.to_string(),
+            created_at: Utc::now().timestamp(),
+            user: username,
+        }
+        .insert(&ALISTRAL_CLIENT.interzic.database_client)
+        .await?;
+
+        history
+            .set_recordings(
+                &ALISTRAL_CLIENT.interzic.database_client,
+                &playlist.recordings,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    fn get_output_file(&self) -> Result<&Path, crate::Error> {
+        self.output_file
+            .as_deref()
+            .ok_or(crate::Error::MissingPlaylistUserDataError(
+                "output file".to_string(),
+            ))
+    }
+}
+
+/// Get the id of a youtube playlist from its URL, or return the input if it's already an id
+fn get_youtube_playlist_id(input: &str) -> String {
+    input
+        .split_once("list=")
+        .map(|(_, id)| id.split('&').next().unwrap_or(id).to_string())
+        .unwrap_or_else(|| input.to_string())
+}
@@ -0,0 +1,36 @@
+use std::collections::HashSet;
+
+use alistral_core::datastructures::entity_with_listens::recording::RecordingWithListens;
+use async_fn_stream::fn_stream;
+use futures::pin_mut;
+use futures::Stream;
+use futures::StreamExt;
+use interzic::models::recording_feedback::RecordingFeedback;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::models::error::ResultTEExt as _;
+
+/// Remove the recordings that the user hated on ListenBrainz. The feedback is read from the cache (See `cache sync-feedback`)
+pub fn hated_filter(
+    recordings: impl Stream<Item = RecordingWithListens>,
+    username: String,
+) -> impl Stream<Item = RecordingWithListens> {
+    fn_stream(|emitter| async move {
+        let hated: HashSet<String> = RecordingFeedback::find_mbids_by_score(
+            &ALISTRAL_CLIENT.interzic.database_client,
+            &username,
+            -1,
+        )
+        .await
+        .expect_fatal("Couldn't load the user's feedback")
+        .into_iter()
+        .collect();
+
+        pin_mut!(recordings);
+        while let Some(recording) = recordings.next().await {
+            if !hated.contains(&recording.recording().mbid) {
+                emitter.emit(recording).await;
+            }
+        }
+    })
+}
@@ -1,15 +1,85 @@
+use core::pin::Pin;
+
+use alistral_core::datastructures::entity_with_listens::recording::RecordingWithListens;
+use chrono::Duration;
+use futures::Stream;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::datastructures::radio::filters::cooldown::cooldown_filter;
+use crate::datastructures::radio::filters::hated::hated_filter;
+use crate::datastructures::radio::filters::min_listens::min_listen_filter;
+use crate::datastructures::radio::filters::recently_suggested::recently_suggested_filter;
+use crate::datastructures::radio::filters::timeouts::timeout_filter;
+
 pub mod booleans;
 pub mod cooldown;
+pub mod hated;
 pub mod min_listens;
+pub mod recently_suggested;
 pub mod timeouts;
 
+/// A filter step of a radio recipe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
 pub enum RadioFilters {
+    /// Remove the recordings with less listens than the value
     MinListens(u64),
+
+    /// Remove the recordings listened in the last X hours
+    Cooldown(u64),
+
+    /// Remove the recordings that are timed out
     Timeouts,
+
+    /// Remove the recordings that got suggested by a radio in the last X days
+    RecentlySuggested(u64),
+
+    /// Remove the recordings that the user hated on ListenBrainz
+    Hated,
 }
 
-// impl RadioFilters {
-//     pub fn filter(self, recordings: impl StreamExt<Item = RecordingWithListens>) -> impl Stream<Item = RecordingWithListens> {
+impl RadioFilters {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::MinListens(_) => "min_listens",
+            Self::Cooldown(_) => "cooldown",
+            Self::Timeouts => "timeouts",
+            Self::RecentlySuggested(_) => "recently_suggested",
+            Self::Hated => "hated",
+        }
+    }
 
-//     }
-// }
+    /// Get why a recording got removed by this filter
+    pub fn get_removal_reason(&self) -> String {
+        match self {
+            Self::MinListens(count) => format!("It has less than {count} listens"),
+            Self::Cooldown(hours) => format!("It has been listened in the last {hours} hours"),
+            Self::Timeouts => "It is timed out".to_string(),
+            Self::RecentlySuggested(days) => {
+                format!("It has been put in a radio in the last {days} days")
+            }
+            Self::Hated => "It is hated by the user".to_string(),
+        }
+    }
+
+    pub fn filter<'a>(
+        &self,
+        username: &str,
+        recordings: impl Stream<Item = RecordingWithListens> + 'a,
+    ) -> Pin<Box<dyn Stream<Item = RecordingWithListens> + 'a>> {
+        match self {
+            Self::MinListens(count) => Box::pin(min_listen_filter(recordings, *count)),
+            Self::Cooldown(hours) => {
+                Box::pin(cooldown_filter(recordings, Duration::hours(*hours as i64)))
+            }
+            Self::Timeouts => Box::pin(timeout_filter(recordings)),
+            Self::RecentlySuggested(days) => Box::pin(recently_suggested_filter(
+                recordings,
+                username.to_string(),
+                Duration::days(*days as i64),
+            )),
+            Self::Hated => Box::pin(hated_filter(recordings, username.to_string())),
+        }
+    }
+}
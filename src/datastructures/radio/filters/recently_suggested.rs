@@ -0,0 +1,40 @@
+use std::collections::HashSet;
+
+use alistral_core::datastructures::entity_with_listens::recording::RecordingWithListens;
+use async_fn_stream::fn_stream;
+use chrono::Duration;
+use chrono::Utc;
+use futures::pin_mut;
+use futures::Stream;
+use futures::StreamExt;
+use interzic::models::playlist_history::PlaylistHistory;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::models::error::ResultTEExt as _;
+
+/// Remove the recordings that got put in a radio playlist of the user during the period
+pub fn recently_suggested_filter(
+    recordings: impl Stream<Item = RecordingWithListens>,
+    username: String,
+    period: Duration,
+) -> impl Stream<Item = RecordingWithListens> {
+    fn_stream(|emitter| async move {
+        let since = (Utc::now() - period).timestamp();
+        let suggested: HashSet<String> = PlaylistHistory::find_recording_mbids_since(
+            &ALISTRAL_CLIENT.interzic.database_client,
+            &username,
+            since,
+        )
+        .await
+        .expect_fatal("Couldn't load the radio history")
+        .into_iter()
+        .collect();
+
+        pin_mut!(recordings);
+        while let Some(recording) = recordings.next().await {
+            if !suggested.contains(&recording.recording().mbid) {
+                emitter.emit(recording).await;
+            }
+        }
+    })
+}
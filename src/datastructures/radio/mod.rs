@@ -1,4 +1,7 @@
 pub mod collector;
+pub mod exporter;
 pub mod filters;
+pub mod recipe;
+pub mod report;
 pub mod seeders;
 pub mod sorters;
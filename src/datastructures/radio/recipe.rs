@@ -0,0 +1,153 @@
+use std::fs;
+use std::path::Path;
+
+use itertools::Itertools as _;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::datastructures::radio::filters::RadioFilters;
+use crate::datastructures::radio::seeders::entity::EntitySeeder;
+use crate::datastructures::radio::seeders::listens::ListenSeeder;
+use crate::datastructures::radio::seeders::listens::ListenSeederBuilder;
+use crate::datastructures::radio::seeders::SeederSettingsBuilder;
+use crate::datastructures::radio::sorters::RadioSorters;
+use crate::models::cli::common::Timeframe;
+use crate::models::config::Config;
+
+/// A user defined radio, made from a seeder, a list of filters, and a sorter.
+///
+/// Recipes are written in TOML:
+/// ```toml
+/// name = "Forgotten favorites"
+///
+/// [seeder]
+/// type = "listens"
+/// seed_listen_range = "last365-days"
+///
+/// [[filters]]
+/// type = "min_listens"
+/// value = 5
+///
+/// [[filters]]
+/// type = "timeouts"
+///
+/// [sorter]
+/// type = "overdue_factor"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RadioRecipe {
+    /// The name of the radio. This is used as the title of the playlist
+    pub name: Option<String>,
+
+    pub seeder: RecipeSeeder,
+
+    /// The filters to apply, in order
+    #[serde(default)]
+    pub filters: Vec<RadioFilters>,
+
+    pub sorter: RadioSorters,
+}
+
+impl RadioRecipe {
+    /// Create the recipe of a built-in radio. The seeder is given separately to the radio, so the recipe's seeder is left empty
+    pub fn new(name: &str, filters: Vec<RadioFilters>, sorter: RadioSorters) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            seeder: RecipeSeeder::Listens {
+                username: None,
+                seed_listen_range: None,
+                min_seed_listens: None,
+                restrict_to: Vec::new(),
+            },
+            filters,
+            sorter,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, crate::Error> {
+        let content = fs::read_to_string(path).map_err(crate::Error::RadioRecipeLoadError)?;
+        let recipe: Self = toml::from_str(&content).map_err(crate::Error::RadioRecipeParseError)?;
+
+        recipe.validate()?;
+        Ok(recipe)
+    }
+
+    fn validate(&self) -> Result<(), crate::Error> {
+        if self
+            .name
+            .as_ref()
+            .is_some_and(|name| name.trim().is_empty())
+        {
+            return Err(crate::Error::RadioRecipeValidationError(
+                "The name of the radio cannot be empty".to_string(),
+            ));
+        }
+
+        // Having the same filter twice is most likely a copy paste mistake
+        if let Some(duplicate) = self
+            .filters
+            .iter()
+            .map(RadioFilters::get_name)
+            .duplicates()
+            .next()
+        {
+            return Err(crate::Error::RadioRecipeValidationError(format!(
+                "The filter `{duplicate}` is set more than once"
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Where to get the recordings of the radio from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum RecipeSeeder {
+    /// Use the recordings listened by the user
+    Listens {
+        /// Name of the user to fetch listens from. Uses the default user if unset
+        username: Option<String>,
+
+        /// What time range of listens to use as reference. Ex: `"last365-days"`, `"2022"`, `"last summer"`, `"2022-06..2022-09"`
+        seed_listen_range: Option<Timeframe>,
+
+        /// When used with `seed_listen_range`, how many listens should be given as a minimum, even if they are outside of the range
+        min_seed_listens: Option<u64>,
+
+        /// Only keep the recordings related to those entities. Ex: `restrict_to = [{ type = "artist", value = "Daft Punk" }]`
+        #[serde(default)]
+        restrict_to: Vec<EntitySeeder>,
+    },
+}
+
+impl RecipeSeeder {
+    /// Create the seeder described by the recipe, without any override from the command line
+    pub fn get_listen_seeder(&self) -> ListenSeeder {
+        match self {
+            Self::Listens {
+                username,
+                seed_listen_range,
+                min_seed_listens,
+                restrict_to,
+            } => {
+                let settings = SeederSettingsBuilder::default()
+                    .min_listen_per_recording(min_seed_listens.unwrap_or(3))
+                    .min_listened_at(seed_listen_range.and_then(|r| r.get_start_date()))
+                    .max_listened_at(seed_listen_range.and_then(|r| r.get_end_date()))
+                    .build();
+
+                let username = Config::check_username(username);
+                let token = Config::get_saved_token(&username);
+
+                ListenSeederBuilder::default()
+                    .username(username)
+                    .settings(settings)
+                    .restrict_to(restrict_to.clone())
+                    .token(token)
+                    .build()
+            }
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use alistral_core::datastructures::entity_with_listens::recording::collection::RecordingWithListensCollection;
+use alistral_core::datastructures::entity_with_listens::recording::RecordingWithListens;
+use alistral_core::datastructures::listen_collection::traits::ListenCollectionReadable as _;
+use chrono::Duration;
+use chrono::Local;
+use futures::stream;
+use futures::StreamExt as _;
+use interzic::models::playlist_stub::PlaylistStub;
+use itertools::Itertools as _;
+use rust_decimal::Decimal;
+
+use crate::datastructures::radio::filters::RadioFilters;
+use crate::models::config::Config;
+use crate::utils::cli::read_mbid_from_input;
+
+/// The steps a radio took to generate its playlist. This is used to explain the playlist with `--dry-run` and `--explain`
+#[derive(Debug, Clone, Default)]
+pub struct RadioReport {
+    /// The user the radio is made for
+    username: String,
+
+    /// The recordings given by the seeder
+    seed: Vec<RecordingWithListens>,
+
+    /// The filters applied to the seed, in order
+    filters: Vec<RadioFilters>,
+
+    /// The MBIDs of the filtered recordings with their sorter score, in the order given by the sorter
+    sorted: Vec<(String, Decimal)>,
+}
+
+impl RadioReport {
+    pub fn new(
+        username: &str,
+        seed: &RecordingWithListensCollection,
+        filters: Vec<RadioFilters>,
+    ) -> Self {
+        Self {
+            username: username.to_string(),
+            seed: seed.iter().cloned().collect_vec(),
+            filters,
+            sorted: Vec::new(),
+        }
+    }
+
+    pub fn set_sorted(&mut self, recordings: &[(RecordingWithListens, Decimal)]) {
+        self.sorted = recordings
+            .iter()
+            .map(|(r, score)| (r.recording().mbid.clone(), *score))
+            .collect_vec();
+    }
+
+    fn get_seed(&self, mbid: &str) -> Option<&RecordingWithListens> {
+        self.seed.iter().find(|r| r.recording().mbid == mbid)
+    }
+
+    /// Get the rank of the recording after sorting, starting at 1
+    fn get_rank(&self, mbid: &str) -> Option<usize> {
+        self.sorted
+            .iter()
+            .position(|(id, _)| id == mbid)
+            .map(|pos| pos + 1)
+    }
+
+    /// Get the score given to the recording by the sorter
+    fn get_score(&self, mbid: &str) -> Option<Decimal> {
+        self.sorted
+            .iter()
+            .find(|(id, _)| id == mbid)
+            .map(|(_, score)| *score)
+    }
+
+    /// Get the first filter that removes the recording
+    async fn get_removing_filter(&self, recording: &RecordingWithListens) -> Option<&RadioFilters> {
+        for filter in &self.filters {
+            let kept = filter
+                .filter(&self.username, stream::iter([recording.clone()]))
+                .next()
+                .await
+                .is_some();
+
+            if !kept {
+                return Some(filter);
+            }
+        }
+
+        None
+    }
+
+    /// Print the tracks of the playlist, with the data used to select them.
+    ///
+    /// This only reads the data the radio already used, so it doesn't send any request
+    pub fn print_tracks(&self, playlist: &PlaylistStub) {
+        let filters = if self.filters.is_empty() {
+            "-".to_string()
+        } else {
+            self.filters.iter().map(RadioFilters::get_name).join(", ")
+        };
+
+        let conf = Config::load_or_panic();
+
+        println!(
+            "{:>3}  {:<50}  {:>7}  {:>11}  {:>7}  {:>7}  {:>4}  {:>10}  {:>5}  Filters passed",
+            "#",
+            "Recording",
+            "Listens",
+            "Last listen",
+            "Overdue",
+            "Rate/yr",
+            "Bump",
+            "Score",
+            "Rank"
+        );
+
+        for (i, track) in playlist.recordings.iter().enumerate() {
+            let position = i + 1;
+            let name = truncate(&format!("{} - {}", track.title, track.artist_credits), 50);
+
+            let Some(mbid) = &track.mbid else {
+                println!("{position:>3}  {name:<50}");
+                continue;
+            };
+
+            let bump = conf
+                .read_or_panic()
+                .bumps
+                .get_multiplier(&self.username, mbid);
+            let rank = self
+                .get_rank(mbid)
+                .map(|rank| rank.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            let score = self
+                .get_score(mbid)
+                .map(|score| score.round_dp(2).to_string())
+                .unwrap_or_else(|| "-".to_string());
+
+            let Some(recording) = self.get_seed(mbid) else {
+                println!(
+                    "{position:>3}  {name:<50}  {:>7}  {:>11}  {:>7}  {:>7}  {bump:>4}  {score:>10}  {rank:>5}  -",
+                    0, "-", "-", "-"
+                );
+                continue;
+            };
+
+            let listens = recording.listen_count();
+            let last_listen = recording
+                .latest_listen_date()
+                .map(|date| date.with_timezone(&Local).format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| "-".to_string());
+            let overdue = recording.overdue_factor().round_dp(2);
+            let rate = recording
+                .get_listen_rate(Duration::days(365))
+                .map(|rate| rate.round_dp(2).to_string())
+                .unwrap_or_else(|| "-".to_string());
+
+            println!(
+                "{position:>3}  {name:<50}  {listens:>7}  {last_listen:>11}  {overdue:>7}  {rate:>7}  {bump:>4}  {score:>10}  {rank:>5}  {filters}"
+            );
+        }
+    }
+
+    /// Print why the recording is, or isn't in the playlist
+    pub async fn explain(&self, input: &str, playlist: &PlaylistStub) -> Result<(), crate::Error> {
+        let Some(mbid) = read_mbid_from_input(input) else {
+            println!("`{input}` isn't a recording MBID or URL");
+            return Ok(());
+        };
+
+        println!("Explaining recording {mbid}:");
+
+        if let Some(pos) = playlist
+            .recordings
+            .iter()
+            .position(|r| r.mbid.as_ref() == Some(&mbid))
+        {
+            println!(" - It is in the playlist, at position {}", pos + 1);
+            return Ok(());
+        }
+
+        if self.seed.is_empty() {
+            println!(" - It isn't in the playlist. This radio doesn't keep track of its steps, so no more details can be given");
+            return Ok(());
+        }
+
+        let Some(recording) = self.get_seed(&mbid) else {
+            println!(" - It isn't part of the seed. Either it hasn't been listened during the seed listen range, or it isn't related to the `--seed-*` entities");
+            return Ok(());
+        };
+
+        println!(
+            " - It is part of the seed, with {} listens",
+            recording.listen_count()
+        );
+
+        if let Some(filter) = self.get_removing_filter(recording).await {
+            println!(
+                " - It got removed by the filter `{}`: {}",
+                filter.get_name(),
+                filter.get_removal_reason()
+            );
+            return Ok(());
+        }
+
+        println!(" - It passed all the filters");
+
+        let Some(rank) = self.get_rank(&mbid) else {
+            println!(" - It wasn't given to the sorter");
+            return Ok(());
+        };
+
+        if let Some(score) = self.get_score(&mbid) {
+            println!(" - The sorter gave it a score of {}", score.round_dp(2));
+        }
+
+        // The rank of the last recording that got into the playlist
+        let last_rank = playlist
+            .recordings
+            .iter()
+            .filter_map(|r| r.mbid.as_deref())
+            .filter_map(|mbid| self.get_rank(mbid))
+            .max()
+            .unwrap_or(0);
+
+        if rank < last_rank {
+            println!(
+                " - It got ranked #{rank} by the sorter, but got left out as it breaks the playlist's constraints (`--max-per-artist`, `--max-per-release-group`, `--artist-spacing`, `--max-duration`)"
+            );
+        } else {
+            println!(
+                " - It got ranked #{rank} out of {} by the sorter, but the playlist got filled by the first {last_rank} recordings",
+                self.sorted.len()
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn truncate(value: &str, max: usize) -> String {
+    if value.chars().count() <= max {
+        return value.to_string();
+    }
+
+    let mut out = value.chars().take(max - 1).collect::<String>();
+    out.push('…');
+    out
+}
@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+
+use alistral_core::datastructures::entity_with_listens::recording::collection::RecordingWithListensCollection;
+use interzic::models::services::jspf::Jspf;
+use interzic::models::services::listenbrainz::Listenbrainz;
+use itertools::Itertools as _;
+use musicbrainz_db_lite::models::musicbrainz::recording::Recording;
+use musicbrainz_db_lite::models::musicbrainz::release::Release;
+use musicbrainz_rs::entity::recording::Recording as MBRecording;
+use musicbrainz_rs::entity::release::Release as MBRelease;
+use musicbrainz_rs::Browse as _;
+use musicbrainz_rs::Search as _;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::info;
+use tracing::instrument;
+use tuillez::pg_counted;
+use tuillez::pg_inc;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::api::musicbrainz::browse_all;
+use crate::api::musicbrainz::BROWSE_LIMIT;
+use crate::database::listenbrainz::prefetching::fetch_recordings_as_complete;
+use crate::database::listenbrainz::prefetching::prefetch_releases;
+use crate::utils::cli::read_mbid_from_input;
+
+/// The number of recordings to check in a single tag search
+const TAG_SEARCH_CHUNK_SIZE: usize = 25;
+
+/// Restrict the seeded recordings to the ones related to a MusicBrainz entity.
+///
+/// Artists, releases, release groups and tags can be given as an MBID, a MusicBrainz URL, or a name (Case insensitive).
+/// Collections can only be given as an MBID or URL.
+/// Playlists can be given as a ListenBrainz playlist MBID or URL, or as the path to a JSPF file. Private playlists need the token of their owner.
+///
+/// Only the recordings that the user listened to are kept, as a radio needs listens to sort them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum EntitySeeder {
+    /// Keep the recordings credited to this artist
+    Artist(String),
+
+    /// Keep the recordings appearing on this release
+    Release(String),
+
+    /// Keep the recordings appearing on any release of this release group
+    ReleaseGroup(String),
+
+    /// Keep the recordings of a recording or release collection
+    Collection(String),
+
+    /// Keep the recordings tagged with this tag or genre
+    Tag(String),
+
+    /// Keep the recordings of a ListenBrainz playlist, or of a JSPF file
+    Playlist(String),
+}
+
+impl EntitySeeder {
+    /// Remove the recordings that aren't related to the entity
+    #[instrument(skip(conn, recordings, token), fields(indicatif.pb_show = tracing::field::Empty))]
+    pub async fn restrict(
+        &self,
+        conn: &mut sqlx::SqliteConnection,
+        recordings: RecordingWithListensCollection,
+        token: Option<&str>,
+    ) -> Result<RecordingWithListensCollection, crate::Error> {
+        let kept_ids = {
+            let recording_refs = recordings.iter_entities().collect_vec();
+            fetch_recordings_as_complete(conn, &recording_refs).await?;
+
+            match self {
+                Self::Artist(value) => {
+                    info!("[Seeding] Keeping the recordings of artist `{value}`");
+                    Self::get_artist_recordings(conn, &recording_refs, value).await?
+                }
+                Self::Release(value) => {
+                    info!("[Seeding] Keeping the recordings of release `{value}`");
+                    Self::get_release_recordings(conn, &recording_refs, value).await?
+                }
+                Self::ReleaseGroup(value) => {
+                    info!("[Seeding] Keeping the recordings of release group `{value}`");
+                    Self::get_release_group_recordings(conn, &recording_refs, value).await?
+                }
+                Self::Collection(value) => {
+                    info!("[Seeding] Keeping the recordings of collection `{value}`");
+                    Self::get_collection_recordings(conn, &recording_refs, value).await?
+                }
+                Self::Tag(value) => {
+                    info!("[Seeding] Keeping the recordings tagged `{value}`");
+                    Self::get_tagged_recordings(&recording_refs, value).await?
+                }
+                Self::Playlist(value) => {
+                    info!("[Seeding] Keeping the recordings of playlist `{value}`");
+                    Self::get_playlist_recordings(&recording_refs, value, token).await?
+                }
+            }
+        };
+
+        Ok(recordings
+            .into_iter()
+            .filter(|r| kept_ids.contains(&r.recording().id))
+            .collect_vec()
+            .into())
+    }
+
+    async fn get_artist_recordings(
+        conn: &mut sqlx::SqliteConnection,
+        recordings: &[&Recording],
+        value: &str,
+    ) -> Result<HashSet<i64>, crate::Error> {
+        let results = Recording::get_artist_from_credits_as_batch(conn, recordings).await?;
+        let mut out = HashSet::new();
+
+        for (_, (recording, artists)) in results {
+            if artists
+                .iter()
+                .any(|artist| is_matching_entity(value, &artist.mbid, &artist.name))
+            {
+                out.insert(recording.id);
+            }
+        }
+
+        Ok(out)
+    }
+
+    async fn get_release_recordings(
+        conn: &mut sqlx::SqliteConnection,
+        recordings: &[&Recording],
+        value: &str,
+    ) -> Result<HashSet<i64>, crate::Error> {
+        let results = Recording::get_releases_as_batch(conn, recordings).await?;
+        let mut out = HashSet::new();
+
+        for (_, (recording, releases)) in results {
+            if releases
+                .iter()
+                .any(|release| is_matching_entity(value, &release.mbid, &release.title))
+            {
+                out.insert(recording.id);
+            }
+        }
+
+        Ok(out)
+    }
+
+    async fn get_release_group_recordings(
+        conn: &mut sqlx::SqliteConnection,
+        recordings: &[&Recording],
+        value: &str,
+    ) -> Result<HashSet<i64>, crate::Error> {
+        let results = Recording::get_releases_as_batch(conn, recordings).await?;
+
+        // Remember which recordings are on which release
+        let mut recordings_of_release: HashMap<i64, Vec<i64>> = HashMap::new();
+        let mut releases = Vec::new();
+        for (_, (recording, recording_releases)) in results {
+            for release in recording_releases {
+                recordings_of_release
+                    .entry(release.id)
+                    .or_default()
+                    .push(recording.id);
+                releases.push(release);
+            }
+        }
+
+        let release_refs = releases.iter().unique_by(|r| r.id).collect_vec();
+        prefetch_releases(conn, &release_refs).await?;
+
+        let results = Release::get_release_groups_as_batch(conn, &release_refs).await?;
+        let mut out = HashSet::new();
+
+        for (_, (release, release_groups)) in results {
+            if release_groups
+                .iter()
+                .any(|group| is_matching_entity(value, &group.mbid, &group.title))
+            {
+                if let Some(ids) = recordings_of_release.get(&release.id) {
+                    out.extend(ids.iter().copied());
+                }
+            }
+        }
+
+        Ok(out)
+    }
+
+    async fn get_collection_recordings(
+        conn: &mut sqlx::SqliteConnection,
+        recordings: &[&Recording],
+        value: &str,
+    ) -> Result<HashSet<i64>, crate::Error> {
+        let collection = read_mbid_from_input(value).ok_or_else(|| {
+            crate::Error::RadioSeederError(format!("`{value}` isn't a valid collection MBID"))
+        })?;
+
+        let collection = collection.as_str();
+
+        // Collections can either hold recordings or releases. Fetch both, as one of them will be empty
+        let recording_mbids: HashSet<String> = browse_all(|offset| async move {
+            MBRecording::browse()
+                .by_collection(collection)
+                .limit(BROWSE_LIMIT)
+                .offset(offset)
+                .execute_with_client(&ALISTRAL_CLIENT.musicbrainz_rs)
+                .await
+        })
+        .await?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+
+        let release_mbids: HashSet<String> = browse_all(|offset| async move {
+            MBRelease::browse()
+                .by_collection(collection)
+                .limit(BROWSE_LIMIT)
+                .offset(offset)
+                .execute_with_client(&ALISTRAL_CLIENT.musicbrainz_rs)
+                .await
+        })
+        .await?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+
+        let mut out: HashSet<i64> = recordings
+            .iter()
+            .filter(|r| recording_mbids.contains(&r.mbid))
+            .map(|r| r.id)
+            .collect();
+
+        if !release_mbids.is_empty() {
+            let results = Recording::get_releases_as_batch(conn, recordings).await?;
+
+            for (_, (recording, releases)) in results {
+                if releases.iter().any(|r| release_mbids.contains(&r.mbid)) {
+                    out.insert(recording.id);
+                }
+            }
+        }
+
+        Ok(out)
+    }
+
+    async fn get_tagged_recordings(
+        recordings: &[&Recording],
+        value: &str,
+    ) -> Result<HashSet<i64>, crate::Error> {
+        let tag = value.trim().to_lowercase().replace('"', "");
+        let chunks = recordings.chunks(TAG_SEARCH_CHUNK_SIZE).collect_vec();
+        pg_counted!(chunks.len(), "Searching tagged recordings");
+
+        let mut tagged_mbids = HashSet::new();
+        for chunk in chunks {
+            let ids = chunk.iter().map(|r| format!("\"{}\"", r.mbid)).join(" OR ");
+            let query = format!("tag:\"{tag}\" AND rid:({ids})");
+
+            let result = MBRecording::search(query)
+                .limit(TAG_SEARCH_CHUNK_SIZE as u8)
+                .execute_with_client(&ALISTRAL_CLIENT.musicbrainz_rs)
+                .await?;
+
+            tagged_mbids.extend(result.entities.into_iter().map(|r| r.id));
+            pg_inc!();
+        }
+
+        Ok(recordings
+            .iter()
+            .filter(|r| tagged_mbids.contains(&r.mbid))
+            .map(|r| r.id)
+            .collect())
+    }
+
+    async fn get_playlist_recordings(
+        recordings: &[&Recording],
+        value: &str,
+        token: Option<&str>,
+    ) -> Result<HashSet<i64>, crate::Error> {
+        let path = Path::new(value);
+        let playlist = if path.is_file() {
+            Jspf::load_playlist(path)?
+        } else {
+            let playlist_mbid = read_mbid_from_input(value).ok_or_else(|| {
+                crate::Error::RadioSeederError(format!(
+                    "`{value}` is neither a JSPF file nor a valid playlist MBID"
+                ))
+            })?;
+
+            Listenbrainz::fetch_playlist(&ALISTRAL_CLIENT.interzic, &playlist_mbid, token).await?
+        };
+
+        let playlist_mbids: HashSet<String> = playlist.get_recording_mbids().into_iter().collect();
+
+        Ok(recordings
+            .iter()
+            .filter(|r| playlist_mbids.contains(&r.mbid))
+            .map(|r| r.id)
+            .collect())
+    }
+}
+
+/// Check if the entity is the one the user asked for, either by MBID or by name
+fn is_matching_entity(value: &str, mbid: &str, name: &str) -> bool {
+    match read_mbid_from_input(value) {
+        Some(id) => id == mbid,
+        None => value.trim().to_lowercase() == name.to_lowercase(),
+    }
+}
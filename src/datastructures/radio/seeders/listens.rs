@@ -9,6 +9,7 @@ use musicbrainz_db_lite::models::listenbrainz::listen::Listen;
 use crate::api::clients::ALISTRAL_CLIENT;
 use crate::database::listenbrainz::listens::fetch_latest_listens_of_user;
 
+use super::entity::EntitySeeder;
 use super::SeederSettings;
 
 /// A querry to generate a list of seed recording using the user's listens
@@ -19,6 +20,13 @@ pub struct ListenSeeder {
 
     #[builder(Default=!)]
     settings: SeederSettings,
+
+    /// Only keep the recordings related to all of those entities
+    restrict_to: Vec<EntitySeeder>,
+
+    /// The token of the user, used to read their private playlists
+    #[builder(Option=!)]
+    token: Option<String>,
 }
 
 impl ListenSeeder {
@@ -78,6 +86,12 @@ impl ListenSeeder {
         let minimum_listens = self.get_minimum_listens(conn).await?;
         recordings.insert_or_merge(minimum_listens);
 
+        for entity in &self.restrict_to {
+            recordings = entity
+                .restrict(conn, recordings, self.token.as_deref())
+                .await?;
+        }
+
         Ok(recordings)
     }
 
@@ -96,7 +110,12 @@ impl ListenSeeder {
             .min_listened_at
             .map(|date| date.timestamp())
             .unwrap_or(0);
-        let listens: ListenCollection = sqlx::query_as!(
+        let before_date = self
+            .settings
+            .max_listened_at
+            .map(|date| date.timestamp())
+            .unwrap_or(i64::MAX);
+        let listens: Vec<Listen> = sqlx::query_as!(
             Listen,
             "
             SELECT 
@@ -118,8 +137,14 @@ impl ListenSeeder {
             after_date
         )
         .fetch_all(&mut *conn)
-        .await?
-        .into();
+        .await?;
+
+        // Don't give listens made after the range, or recordings only listened after it would get seeded
+        let listens: ListenCollection = listens
+            .into_iter()
+            .filter(|listen| listen.listened_at <= before_date)
+            .collect_vec()
+            .into();
 
         let mapped =
             RecordingWithListensCollection::from_listencollection(conn, &ALISTRAL_CLIENT, listens)
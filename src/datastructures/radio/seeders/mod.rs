@@ -2,6 +2,7 @@ use chrono::DateTime;
 use chrono::Utc;
 use macon::Builder;
 
+pub mod entity;
 pub mod listens;
 
 #[derive(Debug, Clone, Default, Builder)]
@@ -0,0 +1,86 @@
+use core::fmt::Display;
+
+use alistral_core::datastructures::entity_with_listens::release_group::collection::ReleaseGroupWithListensCollection;
+use alistral_core::datastructures::entity_with_listens::release_group::ReleaseGroupWithListens;
+use alistral_core::datastructures::listen_collection::traits::ListenCollectionReadable as _;
+use chrono::Duration;
+use clap::ValueEnum;
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+use crate::datastructures::radio::sorters::weighted::sort_by_score;
+use crate::datastructures::radio::sorters::weighted::ScoreOrder;
+use crate::models::data::listenbrainz::popularity::PopularityReleaseGroupResponseItem;
+
+/// How to sort the albums of an album radio
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlbumSorter {
+    /// The albums you should have listened to by now
+    Overdue,
+
+    /// The albums with the lowest listen rate
+    Rate,
+
+    /// The albums that you listen to, but seemingly no one else does
+    Underrated,
+}
+
+impl Display for AlbumSorter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Overdue => write!(f, "overdue"),
+            Self::Rate => write!(f, "rate"),
+            Self::Underrated => write!(f, "underrated"),
+        }
+    }
+}
+
+/// Sort the albums by the time overdue / the average time between listens
+pub fn album_overdue_sorter(
+    albums: Vec<ReleaseGroupWithListens>,
+    temperature: Option<f64>,
+) -> Vec<ReleaseGroupWithListens> {
+    sort_by_score(
+        albums,
+        |r| r.overdue_factor() + Decimal::ONE,
+        ScoreOrder::Descending,
+        temperature,
+    )
+}
+
+/// Sort the albums by their lowest listen rate
+pub fn album_listen_rate_sorter(
+    albums: Vec<ReleaseGroupWithListens>,
+    temperature: Option<f64>,
+) -> Vec<ReleaseGroupWithListens> {
+    sort_by_score(
+        albums,
+        |r| {
+            r.get_listen_rate(Duration::days(365))
+                .unwrap_or_else(|| Decimal::from(2147483646))
+        },
+        ScoreOrder::Ascending,
+        temperature,
+    )
+}
+
+/// Sort the albums by their underrated score
+pub fn album_underrated_sorter(
+    albums: Vec<ReleaseGroupWithListens>,
+    user_listens: &ReleaseGroupWithListensCollection,
+    global_listen_counts: Vec<PopularityReleaseGroupResponseItem>,
+    temperature: Option<f64>,
+) -> Vec<ReleaseGroupWithListens> {
+    let score = |r: &ReleaseGroupWithListens| {
+        let global_count = global_listen_counts
+            .iter()
+            .find(|c| c.release_group_mbid == r.release_group().mbid)
+            .and_then(|c| c.total_listen_count)
+            .unwrap_or(0);
+
+        r.get_underated_score(user_listens, global_count)
+    };
+
+    sort_by_score(albums, score, ScoreOrder::Descending, temperature)
+}
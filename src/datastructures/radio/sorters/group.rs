@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use alistral_core::datastructures::entity_with_listens::recording::RecordingWithListens;
+use alistral_core::datastructures::listen_collection::traits::ListenCollectionReadable as _;
+use itertools::Itertools as _;
+use rust_decimal::prelude::FromPrimitive as _;
+use rust_decimal::Decimal;
+
+use crate::datastructures::radio::sorters::weighted::sort_by_score;
+use crate::datastructures::radio::sorters::weighted::ScoreOrder;
+
+/// Merge the recordings of multiple users, and sort them by the combined affinity of the group.
+///
+/// Only the recordings listened by at least `min_users` users are kept.
+///
+/// The affinity of a user for a recording is its percentile rank in the user's listen counts, so a user with a lot of listens
+/// doesn't weight more than the others. The score of a recording is the average affinity of all the users of the group,
+/// with users that never listened to it counting as 0
+pub fn group_sorter(
+    user_recordings: Vec<Vec<RecordingWithListens>>,
+    min_users: usize,
+    temperature: Option<f64>,
+) -> Vec<RecordingWithListens> {
+    let user_count = user_recordings.len();
+    let mut recordings: HashMap<String, (RecordingWithListens, Vec<f64>)> = HashMap::new();
+
+    for user in user_recordings {
+        let affinities = get_percentile_ranks(&user);
+
+        for (recording, affinity) in user.into_iter().zip(affinities) {
+            recordings
+                .entry(recording.recording().mbid.clone())
+                .or_insert_with(|| (recording, Vec::new()))
+                .1
+                .push(affinity);
+        }
+    }
+
+    let recordings = recordings
+        .into_values()
+        .filter(|(_, affinities)| affinities.len() >= min_users)
+        .map(|(recording, affinities)| {
+            let score = affinities.iter().sum::<f64>() / user_count as f64;
+            (recording, Decimal::from_f64(score).unwrap_or_default())
+        })
+        .collect_vec();
+
+    sort_by_score(
+        recordings,
+        |(_, score)| *score,
+        ScoreOrder::Descending,
+        temperature,
+    )
+    .into_iter()
+    .map(|(recording, _)| recording)
+    .collect_vec()
+}
+
+/// Get the share of the user's recordings that have as many or less listens than each recording
+fn get_percentile_ranks(recordings: &[RecordingWithListens]) -> Vec<f64> {
+    let counts = recordings
+        .iter()
+        .map(|r| r.listen_count())
+        .sorted()
+        .collect_vec();
+
+    recordings
+        .iter()
+        .map(|r| {
+            let below = counts.partition_point(|count| *count <= r.listen_count());
+            below as f64 / counts.len() as f64
+        })
+        .collect_vec()
+}
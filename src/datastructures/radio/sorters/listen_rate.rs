@@ -3,18 +3,37 @@ use alistral_core::datastructures::listen_collection::traits::ListenCollectionRe
 use chrono::Duration;
 use rust_decimal::Decimal;
 
+use crate::datastructures::radio::sorters::loved::LovedBoost;
+use crate::datastructures::radio::sorters::weighted::sort_with_scores;
+use crate::datastructures::radio::sorters::weighted::ScoreOrder;
 use crate::models::config::Config;
 
 /// Sort listens based on the rate of listens of a recording
-pub fn listen_rate_sorter(mut recordings: Vec<RecordingWithListens>) -> Vec<RecordingWithListens> {
+pub fn listen_rate_sorter(
+    recordings: Vec<RecordingWithListens>,
+    username: &str,
+    loved: &LovedBoost,
+    temperature: Option<f64>,
+) -> Vec<(RecordingWithListens, Decimal)> {
     let conf = Config::load_or_panic();
 
-    recordings.sort_by_cached_key(|r| {
-        let score = r
-            .get_listen_rate(Duration::days(365))
-            .unwrap_or_else(|| Decimal::from(2147483646));
-        score * conf.read_or_panic().bumps.get_multiplier(&r.entity().mbid)
-    });
-
-    recordings
+    sort_with_scores(
+        recordings,
+        |r| {
+            let score = r
+                .get_listen_rate(Duration::days(365))
+                .unwrap_or_else(|| Decimal::from(2147483646));
+            loved.boost(
+                score
+                    * conf
+                        .read_or_panic()
+                        .bumps
+                        .get_multiplier(username, &r.entity().mbid),
+                &r.entity().mbid,
+                ScoreOrder::Ascending,
+            )
+        },
+        ScoreOrder::Ascending,
+        temperature,
+    )
 }
@@ -0,0 +1,56 @@
+use std::collections::HashSet;
+
+use interzic::models::recording_feedback::RecordingFeedback;
+use rust_decimal::Decimal;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::datastructures::radio::sorters::weighted::ScoreOrder;
+use crate::models::config::Config;
+
+/// The recordings loved by the user on ListenBrainz, and the multiplier to apply to their scores
+#[derive(Debug, Clone, Default)]
+pub struct LovedBoost {
+    loved: HashSet<String>,
+    multiplier: Decimal,
+}
+
+impl LovedBoost {
+    /// Load the loved recordings of the user from the cache (See `cache sync-feedback`)
+    pub async fn load(username: &str) -> Result<Self, crate::Error> {
+        let loved = RecordingFeedback::find_mbids_by_score(
+            &ALISTRAL_CLIENT.interzic.database_client,
+            username,
+            1,
+        )
+        .await?
+        .into_iter()
+        .collect();
+
+        Ok(Self {
+            loved,
+            multiplier: Config::load_or_panic()
+                .read_or_panic()
+                .get_loved_multiplier(),
+        })
+    }
+
+    pub fn get_multiplier(&self, recording_mbid: &str) -> Decimal {
+        if self.loved.contains(recording_mbid) {
+            self.multiplier
+        } else {
+            Decimal::ONE
+        }
+    }
+
+    /// Move the score of a loved recording towards the best scores of the sort order
+    pub fn boost(&self, score: Decimal, recording_mbid: &str, order: ScoreOrder) -> Decimal {
+        let multiplier = self.get_multiplier(recording_mbid);
+
+        // Growing the score only makes it better if the highest scores are the best, and the reverse for negative scores
+        if (order == ScoreOrder::Descending) == score.is_sign_positive() {
+            score * multiplier
+        } else {
+            score.checked_div(multiplier).unwrap_or(score)
+        }
+    }
+}
@@ -1,4 +1,164 @@
+use core::pin::Pin;
+
+use alistral_core::datastructures::entity_with_listens::recording::collection::RecordingWithListensCollection;
+use alistral_core::datastructures::entity_with_listens::recording::RecordingWithListens;
+use chrono::Local;
+use futures::stream;
+use futures::Stream;
+use itertools::Itertools as _;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::info;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::api::listenbrainz::global_listen_counts::get_global_listen_counts;
+use crate::database::listenbrainz::listens::ListenFetchQuery;
+use crate::database::listenbrainz::listens::ListenFetchQueryReturn;
+use crate::datastructures::radio::sorters::listen_rate::listen_rate_sorter;
+use crate::datastructures::radio::sorters::loved::LovedBoost;
+use crate::datastructures::radio::sorters::overdue::overdue_factor_sorter;
+use crate::datastructures::radio::sorters::overdue::overdue_factor_sorter_cumulative;
+use crate::datastructures::radio::sorters::overdue::overdue_sorter;
+use crate::datastructures::radio::sorters::time_of_day::time_of_day_sorter;
+use crate::datastructures::radio::sorters::underrated::underrated_sorter;
+
+pub mod album;
+pub mod group;
 pub mod listen_rate;
+pub mod loved;
 pub mod overdue;
 pub mod shared;
+pub mod time_of_day;
+pub mod transitions;
 pub mod underrated;
+pub mod weighted;
+
+/// The sorting step of a radio recipe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RadioSorters {
+    /// Sort by lowest listen rate
+    ListenRate,
+
+    /// Sort by the time overdue
+    Overdue,
+
+    /// Sort by the time overdue / the average time between listens
+    OverdueFactor {
+        /// Calculate the score at the time the listen will be listened at instead of now
+        #[serde(default)]
+        at_listening_time: bool,
+    },
+
+    /// Sort by the underrated score
+    Underrated,
+
+    /// Sort by how much the recordings are usually listened at the current hour and weekday
+    TimeOfDay {
+        /// Only use the hour of the day, and not the day of the week
+        #[serde(default)]
+        ignore_weekday: bool,
+    },
+}
+
+impl RadioSorters {
+    /// Sort the recordings. Each recording is returned with the score it got sorted by
+    pub async fn sort(
+        &self,
+        conn: &mut sqlx::SqliteConnection,
+        username: &str,
+        recordings: Vec<RecordingWithListens>,
+        temperature: Option<f64>,
+    ) -> Result<Pin<Box<dyn Stream<Item = (RecordingWithListens, Decimal)>>>, crate::Error> {
+        // The underrated score doesn't boost loved recordings
+        let loved = match self {
+            Self::Underrated => LovedBoost::default(),
+            _ => LovedBoost::load(username).await?,
+        };
+
+        match self {
+            Self::ListenRate => {
+                info!("[Sorting] Sorting listen by listen rate duration");
+                Ok(Box::pin(stream::iter(listen_rate_sorter(
+                    recordings,
+                    username,
+                    &loved,
+                    temperature,
+                ))))
+            }
+            Self::Overdue => {
+                info!("[Sorting] Sorting listen by overdue duration");
+                Ok(Box::pin(stream::iter(overdue_sorter(
+                    recordings,
+                    username,
+                    &loved,
+                    temperature,
+                ))))
+            }
+            Self::OverdueFactor {
+                at_listening_time: false,
+            } => {
+                info!("[Sorting] Sorting listen by overdue factor");
+                Ok(Box::pin(stream::iter(overdue_factor_sorter(
+                    recordings,
+                    username,
+                    &loved,
+                    temperature,
+                ))))
+            }
+            Self::OverdueFactor {
+                at_listening_time: true,
+            } => {
+                info!("[Sorting] Sorting listen by overdue factor at listen time");
+                Ok(Box::pin(overdue_factor_sorter_cumulative(
+                    recordings,
+                    username.to_string(),
+                    loved,
+                    temperature,
+                )))
+            }
+            Self::TimeOfDay { ignore_weekday } => {
+                info!("[Sorting] Sorting listen by time of day");
+                Ok(Box::pin(stream::iter(time_of_day_sorter(
+                    recordings,
+                    Local::now(),
+                    !ignore_weekday,
+                    username,
+                    &loved,
+                    temperature,
+                ))))
+            }
+            Self::Underrated => {
+                let user_listens = ListenFetchQuery::builder()
+                    .returns(ListenFetchQueryReturn::Mapped)
+                    .user(username.to_string())
+                    .build()
+                    .fetch(conn)
+                    .await?;
+
+                let user_listens = RecordingWithListensCollection::from_listencollection(
+                    conn,
+                    &ALISTRAL_CLIENT,
+                    user_listens,
+                )
+                .await?;
+
+                info!("[Seeding] Getting global listen counts");
+                let recording_ids = recordings
+                    .iter()
+                    .map(|r| r.recording().mbid.to_string())
+                    .collect_vec();
+                let global_listen_counts = get_global_listen_counts(&recording_ids).await?;
+
+                info!("[Sorting] Calculating underated scores");
+                Ok(Box::pin(stream::iter(underrated_sorter(
+                    recordings,
+                    &user_listens,
+                    global_listen_counts,
+                    temperature,
+                ))))
+            }
+        }
+    }
+}
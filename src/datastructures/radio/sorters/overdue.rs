@@ -1,78 +1,116 @@
-use core::cmp::Reverse;
-
 use alistral_core::datastructures::entity_with_listens::recording::RecordingWithListens;
 use alistral_core::datastructures::listen_collection::traits::ListenCollectionReadable as _;
 use async_fn_stream::fn_stream;
 use chrono::Duration;
 use chrono::Utc;
 use futures::Stream;
+use itertools::Itertools as _;
 use rust_decimal::Decimal;
 
+use crate::datastructures::radio::sorters::loved::LovedBoost;
+use crate::datastructures::radio::sorters::weighted::sort_with_scores;
+use crate::datastructures::radio::sorters::weighted::weighted_pick;
+use crate::datastructures::radio::sorters::weighted::ScoreOrder;
 use crate::models::config::Config;
 
-pub fn overdue_sorter(mut recordings: Vec<RecordingWithListens>) -> Vec<RecordingWithListens> {
+pub fn overdue_sorter(
+    recordings: Vec<RecordingWithListens>,
+    username: &str,
+    loved: &LovedBoost,
+    temperature: Option<f64>,
+) -> Vec<(RecordingWithListens, Decimal)> {
     let conf = Config::load_or_panic();
 
-    recordings.sort_by_cached_key(|r| {
-        let score = r.overdue_by().num_seconds();
-        Decimal::from(score)
-            * conf
-                .read_or_panic()
-                .bumps
-                .get_multiplier(&r.recording().mbid)
-    });
-
-    recordings
+    sort_with_scores(
+        recordings,
+        |r| {
+            let score = r.overdue_by().num_seconds();
+            loved.boost(
+                Decimal::from(score)
+                    * conf
+                        .read_or_panic()
+                        .bumps
+                        .get_multiplier(username, &r.recording().mbid),
+                &r.recording().mbid,
+                ScoreOrder::Ascending,
+            )
+        },
+        ScoreOrder::Ascending,
+        temperature,
+    )
 }
 
 pub fn overdue_factor_sorter(
-    mut recordings: Vec<RecordingWithListens>,
-) -> Vec<RecordingWithListens> {
+    recordings: Vec<RecordingWithListens>,
+    username: &str,
+    loved: &LovedBoost,
+    temperature: Option<f64>,
+) -> Vec<(RecordingWithListens, Decimal)> {
     let conf = Config::load_or_panic();
 
-    recordings.sort_by_cached_key(|r| {
-        let score = r.overdue_factor() + Decimal::ONE;
-        Reverse(
-            score
-                * conf
-                    .read_or_panic()
-                    .bumps
-                    .get_multiplier(&r.recording().mbid),
-        )
-    });
-
-    recordings
+    sort_with_scores(
+        recordings,
+        |r| {
+            let score = r.overdue_factor() + Decimal::ONE;
+            loved.boost(
+                score
+                    * conf
+                        .read_or_panic()
+                        .bumps
+                        .get_multiplier(username, &r.recording().mbid),
+                &r.recording().mbid,
+                ScoreOrder::Descending,
+            )
+        },
+        ScoreOrder::Descending,
+        temperature,
+    )
 }
 
 pub fn overdue_factor_sorter_cumulative(
     mut recordings: Vec<RecordingWithListens>,
-) -> impl Stream<Item = RecordingWithListens> {
+    username: String,
+    loved: LovedBoost,
+    temperature: Option<f64>,
+) -> impl Stream<Item = (RecordingWithListens, Decimal)> {
     let conf = Config::load_or_panic();
 
     fn_stream(|emitter| async move {
         let mut curr_time = Utc::now();
         while !recordings.is_empty() {
-            let top_recording = recordings
+            let scores = recordings
                 .iter()
-                .enumerate()
-                .max_by_key(|r| {
-                    let score = r.1.overdue_factor_at(&curr_time) + Decimal::ONE;
-                    score
-                        * conf
-                            .read_or_panic()
-                            .bumps
-                            .get_multiplier(&r.1.recording().mbid)
+                .map(|r| {
+                    let score = r.overdue_factor_at(&curr_time) + Decimal::ONE;
+                    loved.boost(
+                        score
+                            * conf
+                                .read_or_panic()
+                                .bumps
+                                .get_multiplier(&username, &r.recording().mbid),
+                        &r.recording().mbid,
+                        ScoreOrder::Descending,
+                    )
                 })
-                .expect("There should be at least one recording");
+                .collect_vec();
+
+            let top_index = match temperature {
+                Some(temperature) if temperature > 0.0 => {
+                    weighted_pick(&scores, ScoreOrder::Descending, temperature)
+                }
+                _ => scores.iter().position_max(),
+            }
+            .expect("There should be at least one recording");
 
-            let top_recording = recordings.remove(top_recording.0);
+            let top_score = scores[top_index];
+            let top_recording = recordings.remove(top_index);
 
             curr_time += top_recording
                 .recording()
                 .length_as_duration()
                 .unwrap_or(Duration::zero());
 
-            emitter.emit(top_recording).await;
+            emitter.emit((top_recording, top_score)).await;
         }
     })
 }
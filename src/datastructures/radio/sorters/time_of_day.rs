@@ -0,0 +1,109 @@
+use alistral_core::datastructures::entity_with_listens::recording::RecordingWithListens;
+use alistral_core::datastructures::listen_collection::traits::ListenCollectionReadable;
+use chrono::DateTime;
+use chrono::Datelike as _;
+use chrono::Local;
+use chrono::Timelike as _;
+use rust_decimal::prelude::FromPrimitive as _;
+use rust_decimal::Decimal;
+
+use crate::datastructures::radio::sorters::loved::LovedBoost;
+use crate::datastructures::radio::sorters::weighted::sort_with_scores;
+use crate::datastructures::radio::sorters::weighted::ScoreOrder;
+use crate::models::config::Config;
+
+/// The number of imaginary listens spread evenly across the day and week.
+///
+/// This prevents recordings with only a listen or two from having an extreme profile
+const PRIOR_LISTENS: f64 = 2.0;
+
+/// Sort the recordings by how much their listening habits match the given time
+pub fn time_of_day_sorter(
+    recordings: Vec<RecordingWithListens>,
+    at: DateTime<Local>,
+    use_weekday: bool,
+    username: &str,
+    loved: &LovedBoost,
+    temperature: Option<f64>,
+) -> Vec<(RecordingWithListens, Decimal)> {
+    let conf = Config::load_or_panic();
+
+    sort_with_scores(
+        recordings,
+        |r| {
+            let profile = ListenTimeProfile::from_listens(r);
+            let mut score = profile.hour_affinity(at.hour());
+
+            if use_weekday {
+                score *= profile.weekday_affinity(at.weekday().num_days_from_monday());
+            }
+
+            loved.boost(
+                Decimal::from_f64(score).unwrap_or_default()
+                    * conf
+                        .read_or_panic()
+                        .bumps
+                        .get_multiplier(username, &r.recording().mbid),
+                &r.recording().mbid,
+                ScoreOrder::Descending,
+            )
+        },
+        ScoreOrder::Descending,
+        temperature,
+    )
+}
+
+/// The histogram of the local hours and weekdays a recording got listened at
+#[derive(Debug, Clone, Default)]
+pub struct ListenTimeProfile {
+    hours: [u64; 24],
+
+    /// Starting from monday
+    weekdays: [u64; 7],
+
+    total: u64,
+}
+
+impl ListenTimeProfile {
+    pub fn from_listens(listens: &impl ListenCollectionReadable) -> Self {
+        let mut profile = Self::default();
+
+        for listen in listens.iter_listens() {
+            let date = listen.listened_at_as_datetime().with_timezone(&Local);
+
+            profile.hours[date.hour() as usize] += 1;
+            profile.weekdays[date.weekday().num_days_from_monday() as usize] += 1;
+            profile.total += 1;
+        }
+
+        profile
+    }
+
+    /// How much the recording is listened around this hour, compared to a recording listened at any time of the day.
+    ///
+    /// 1 means no preference, higher means the recording is usually played at this hour. The neighbouring hours count for half,
+    /// so a recording played at 7:50 still matches at 8:10
+    pub fn hour_affinity(&self, hour: u32) -> f64 {
+        let hour = hour as usize;
+        let window = self.hours[hour] as f64
+            + 0.5 * self.hours[(hour + 23) % 24] as f64
+            + 0.5 * self.hours[(hour + 1) % 24] as f64;
+
+        // The window covers 2 hours worth of listens out of 24
+        let expected_share = 2.0 / 24.0;
+        let share = (window + PRIOR_LISTENS * expected_share) / (self.total as f64 + PRIOR_LISTENS);
+
+        share / expected_share
+    }
+
+    /// How much the recording is listened on this weekday, compared to a recording listened any day of the week.
+    ///
+    /// 1 means no preference, higher means the recording is usually played on this day
+    pub fn weekday_affinity(&self, weekday: u32) -> f64 {
+        let expected_share = 1.0 / 7.0;
+        let share = (self.weekdays[weekday as usize] as f64 + PRIOR_LISTENS * expected_share)
+            / (self.total as f64 + PRIOR_LISTENS);
+
+        share / expected_share
+    }
+}
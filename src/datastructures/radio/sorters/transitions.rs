@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use itertools::Itertools as _;
+use musicbrainz_db_lite::models::musicbrainz::recording::Recording;
+use tracing::info;
+use tracing::instrument;
+use tuillez::pg_counted;
+use tuillez::pg_inc;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+
+/// The BPM ratio at which two tracks are considered completely different
+const MAX_BPM_RATIO: f64 = 1.25;
+
+/// Reorder a collected playlist so that each track is followed by the most similar remaining one.
+///
+/// Tracks are compared using their MusicBrainz tags and genres, and optionally the BPM and key given in a CSV file.
+/// The first track of the playlist is kept in place, as it's the best scoring one.
+#[derive(Debug, Clone, Default)]
+pub struct TransitionSorter {
+    /// A CSV file with the `recording_mbid`, `bpm` and `key` columns. Ex: `3a5d9f2c-...,128,A minor`
+    pub features_file: Option<PathBuf>,
+}
+
+impl TransitionSorter {
+    #[instrument(skip(self, conn, recordings), fields(indicatif.pb_show = tracing::field::Empty))]
+    pub async fn sort(
+        &self,
+        conn: &mut sqlx::SqliteConnection,
+        recordings: Vec<Recording>,
+    ) -> Result<Vec<Recording>, crate::Error> {
+        if recordings.len() < 3 {
+            return Ok(recordings);
+        }
+
+        info!("[Sorting] Reordering the playlist for smooth transitions");
+        let mut tags = get_recording_tags(conn, &recordings).await?;
+        let mut features = match &self.features_file {
+            Some(path) => load_features(path)?,
+            None => HashMap::new(),
+        };
+
+        let tracks = recordings
+            .into_iter()
+            .map(|recording| TransitionTrack {
+                tags: tags.remove(&recording.mbid).unwrap_or_default(),
+                features: features.remove(&recording.mbid),
+                recording,
+            })
+            .collect_vec();
+
+        Ok(order_by_nearest(tracks)
+            .into_iter()
+            .map(|track| track.recording)
+            .collect_vec())
+    }
+}
+
+/// A recording with the data used to compare it
+struct TransitionTrack {
+    recording: Recording,
+    tags: HashSet<String>,
+    features: Option<TrackFeatures>,
+}
+
+impl TransitionTrack {
+    /// How different the two tracks are, from 0 (identical) to 1 (nothing in common).
+    ///
+    /// Only the data known for both tracks is used. Tracks without anything to compare are in the middle
+    fn distance(&self, other: &Self) -> f64 {
+        let mut distances = Vec::new();
+
+        if !self.tags.is_empty() && !other.tags.is_empty() {
+            let common = self.tags.intersection(&other.tags).count();
+            let total = self.tags.union(&other.tags).count();
+            distances.push(1.0 - common as f64 / total as f64);
+        }
+
+        if let (Some(this), Some(other)) = (&self.features, &other.features) {
+            if let (Some(a), Some(b)) = (this.bpm, other.bpm) {
+                distances.push(bpm_distance(a, b));
+            }
+
+            if let (Some(a), Some(b)) = (this.key, other.key) {
+                distances.push(a.distance(&b));
+            }
+        }
+
+        if distances.is_empty() {
+            return 0.5;
+        }
+
+        distances.iter().sum::<f64>() / distances.len() as f64
+    }
+}
+
+/// Greedily chain the tracks, starting from the first one
+fn order_by_nearest(mut tracks: Vec<TransitionTrack>) -> Vec<TransitionTrack> {
+    let mut ordered = vec![tracks.remove(0)];
+
+    while !tracks.is_empty() {
+        let last = ordered.last().expect("The playlist has at least one track");
+
+        // On ties, the first track is picked, so the score order is kept
+        let next = tracks
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| last.distance(a).total_cmp(&last.distance(b)))
+            .map(|(i, _)| i)
+            .expect("There are tracks left");
+
+        ordered.push(tracks.remove(next));
+    }
+
+    ordered
+}
+
+/// Get the lowercased tags of the recordings, indexed by MBID. Genres are tags too, so they are included
+async fn get_recording_tags(
+    conn: &mut sqlx::SqliteConnection,
+    recordings: &[Recording],
+) -> Result<HashMap<String, HashSet<String>>, crate::Error> {
+    pg_counted!(recordings.len(), "Getting recording tags");
+
+    let mut out = HashMap::new();
+    for recording in recordings {
+        let tags = recording
+            .get_tags_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db)
+            .await?
+            .into_iter()
+            .map(|tag| tag.name.to_lowercase())
+            .collect();
+
+        out.insert(recording.mbid.clone(), tags);
+        pg_inc!();
+    }
+
+    Ok(out)
+}
+
+/// The audio features of a track, as given by the user
+#[derive(Debug, Clone, Copy)]
+struct TrackFeatures {
+    bpm: Option<f64>,
+    key: Option<MusicalKey>,
+}
+
+/// Read the BPM and keys of the recordings from a CSV file
+fn load_features(path: &Path) -> Result<HashMap<String, TrackFeatures>, crate::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_path(path)
+        .map_err(|err| {
+            crate::Error::RadioTransitionFeaturesError(format!(
+                "Couldn't read `{}`: {err}",
+                path.display()
+            ))
+        })?;
+
+    let header = reader
+        .headers()
+        .map_err(|err| crate::Error::RadioTransitionFeaturesError(err.to_string()))?
+        .iter()
+        .map(str::to_lowercase)
+        .collect_vec();
+
+    let mbid_column = header
+        .iter()
+        .position(|column| column == "recording_mbid" || column == "mbid")
+        .ok_or_else(|| {
+            crate::Error::RadioTransitionFeaturesError(
+                "The file has no `recording_mbid` column".to_string(),
+            )
+        })?;
+    let bpm_column = header.iter().position(|column| column == "bpm");
+    let key_column = header.iter().position(|column| column == "key");
+
+    let mut out = HashMap::new();
+    for (i, row) in reader.records().enumerate() {
+        let row = row.map_err(|err| crate::Error::RadioTransitionFeaturesError(err.to_string()))?;
+        let Some(mbid) = row.get(mbid_column).filter(|mbid| !mbid.is_empty()) else {
+            continue;
+        };
+
+        let bpm = match bpm_column.and_then(|col| row.get(col)) {
+            Some(value) if !value.is_empty() => Some(value.parse::<f64>().map_err(|_| {
+                crate::Error::RadioTransitionFeaturesError(format!(
+                    "Invalid BPM `{value}` on row {}",
+                    i + 2
+                ))
+            })?),
+            _ => None,
+        };
+
+        let key = match key_column.and_then(|col| row.get(col)) {
+            Some(value) if !value.is_empty() => {
+                Some(MusicalKey::parse(value).ok_or_else(|| {
+                    crate::Error::RadioTransitionFeaturesError(format!(
+                        "Invalid key `{value}` on row {}",
+                        i + 2
+                    ))
+                })?)
+            }
+            _ => None,
+        };
+
+        out.insert(mbid.to_string(), TrackFeatures { bpm, key });
+    }
+
+    Ok(out)
+}
+
+/// How different two tempos are, from 0 to 1. Half and double tempos are considered close, as they mix well
+fn bpm_distance(a: f64, b: f64) -> f64 {
+    if a <= 0.0 || b <= 0.0 {
+        return 1.0;
+    }
+
+    let ratio = (a / b).ln().abs() % 2.0_f64.ln();
+    let ratio = ratio.min(2.0_f64.ln() - ratio);
+
+    (ratio / MAX_BPM_RATIO.ln()).min(1.0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MusicalKey {
+    /// The pitch class of the tonic, with C = 0
+    pitch: u8,
+    minor: bool,
+}
+
+impl MusicalKey {
+    /// Parse keys like `A minor`, `Am`, `F# major` or `Bb`
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let mut chars = value.chars();
+
+        let mut pitch: i8 = match chars.next()?.to_ascii_uppercase() {
+            'C' => 0,
+            'D' => 2,
+            'E' => 4,
+            'F' => 5,
+            'G' => 7,
+            'A' => 9,
+            'B' => 11,
+            _ => return None,
+        };
+
+        let mut rest = chars.as_str();
+        if let Some(stripped) = rest.strip_prefix(['#', '♯']) {
+            pitch += 1;
+            rest = stripped;
+        } else if let Some(stripped) = rest.strip_prefix(['b', '♭']) {
+            pitch -= 1;
+            rest = stripped;
+        }
+
+        let minor = match rest.trim().to_lowercase().as_str() {
+            "" | "maj" | "major" => false,
+            "m" | "min" | "minor" => true,
+            _ => return None,
+        };
+
+        Some(Self {
+            pitch: pitch.rem_euclid(12) as u8,
+            minor,
+        })
+    }
+
+    /// The position of the key on the circle of fifths. Minor keys share the position of their relative major
+    fn get_fifths_position(&self) -> u8 {
+        let major_pitch = if self.minor {
+            (self.pitch + 3) % 12
+        } else {
+            self.pitch
+        };
+
+        (major_pitch * 7) % 12
+    }
+
+    /// How different two keys are, from 0 to 1. This follows the harmonic mixing rules: neighbours on the circle of fifths and relative keys are close
+    fn distance(&self, other: &Self) -> f64 {
+        let steps = self
+            .get_fifths_position()
+            .abs_diff(other.get_fifths_position());
+        let steps = steps.min(12 - steps) as f64;
+        let mode_change = if self.minor == other.minor { 0.0 } else { 0.5 };
+
+        (steps + mode_change) / 6.5
+    }
+}
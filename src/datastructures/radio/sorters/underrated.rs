@@ -1,23 +1,25 @@
-use core::cmp::Reverse;
-
 use alistral_core::datastructures::entity_with_listens::recording::collection::RecordingWithListensCollection;
 use alistral_core::datastructures::entity_with_listens::recording::RecordingWithListens;
+use rust_decimal::Decimal;
 use tracing::instrument;
 use tuillez::pg_counted;
 use tuillez::pg_inc;
 
+use crate::datastructures::radio::sorters::weighted::sort_with_scores;
+use crate::datastructures::radio::sorters::weighted::ScoreOrder;
 use crate::models::data::listenbrainz::popularity::PopularityRecordingResponseItem;
 
 /// Sort listens based on the rate of listens of a recording
 #[instrument( fields(indicatif.pb_show = tracing::field::Empty))]
 pub fn underrated_sorter(
-    mut recordings: Vec<RecordingWithListens>,
+    recordings: Vec<RecordingWithListens>,
     user_listens: &RecordingWithListensCollection,
     global_listen_counts: Vec<PopularityRecordingResponseItem>,
-) -> Vec<RecordingWithListens> {
+    temperature: Option<f64>,
+) -> Vec<(RecordingWithListens, Decimal)> {
     pg_counted!(recordings.len(), "Sorting recordings");
 
-    recordings.sort_by_cached_key(|r| {
+    let score = |r: &RecordingWithListens| {
         let global_count = global_listen_counts
             .iter()
             .find_map(|c| {
@@ -32,8 +34,8 @@ pub fn underrated_sorter(
 
         pg_inc!();
 
-        Reverse(score)
-    });
+        score
+    };
 
-    recordings
+    sort_with_scores(recordings, score, ScoreOrder::Descending, temperature)
 }
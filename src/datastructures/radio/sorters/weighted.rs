@@ -0,0 +1,159 @@
+use core::cmp::Ordering;
+
+use itertools::Itertools as _;
+use rand::Rng as _;
+use rust_decimal::prelude::ToPrimitive as _;
+use rust_decimal::Decimal;
+
+/// The weight given to the worst score, so it still has a chance to be picked
+const MIN_WEIGHT: f64 = 0.01;
+
+/// How many interquartile ranges away from the quartiles a score is considered an outlier
+const OUTLIER_FENCE: f64 = 3.0;
+
+/// Which scores should come first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreOrder {
+    /// The lowest scores are the best
+    Ascending,
+
+    /// The highest scores are the best
+    Descending,
+}
+
+/// Sort the items by their score, either strictly or with a weighted shuffle if a temperature is given
+pub fn sort_by_score<T>(
+    items: Vec<T>,
+    score: impl Fn(&T) -> Decimal,
+    order: ScoreOrder,
+    temperature: Option<f64>,
+) -> Vec<T> {
+    sort_with_scores(items, score, order, temperature)
+        .into_iter()
+        .map(|(item, _)| item)
+        .collect_vec()
+}
+
+/// Sort the items by their score like [`sort_by_score`], and keep the score of each item
+pub fn sort_with_scores<T>(
+    items: Vec<T>,
+    score: impl Fn(&T) -> Decimal,
+    order: ScoreOrder,
+    temperature: Option<f64>,
+) -> Vec<(T, Decimal)> {
+    let mut scored = items
+        .into_iter()
+        .map(|item| {
+            let score = score(&item);
+            (item, score)
+        })
+        .collect_vec();
+
+    match temperature {
+        Some(temperature) => weighted_shuffle(scored, |(_, score)| *score, order, temperature),
+        None => {
+            match order {
+                ScoreOrder::Ascending => scored.sort_by_key(|(_, score)| *score),
+                ScoreOrder::Descending => {
+                    scored.sort_by_key(|(_, score)| core::cmp::Reverse(*score))
+                }
+            }
+
+            scored
+        }
+    }
+}
+
+/// Randomly reorder the items, using their scores as sampling weights.
+///
+/// The temperature changes how much the scores matter. Lower temperatures get closer to the strict ordering,
+/// while higher temperatures get closer to a uniform shuffle.
+pub fn weighted_shuffle<T>(
+    items: Vec<T>,
+    score: impl Fn(&T) -> Decimal,
+    order: ScoreOrder,
+    temperature: f64,
+) -> Vec<T> {
+    let scores = items.iter().map(score).collect_vec();
+    let keys = get_sampling_keys(&scores, order, temperature);
+
+    items
+        .into_iter()
+        .zip(keys)
+        .sorted_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal))
+        .map(|(item, _)| item)
+        .collect_vec()
+}
+
+/// Pick the index of an item, using their scores as sampling weights
+pub fn weighted_pick(scores: &[Decimal], order: ScoreOrder, temperature: f64) -> Option<usize> {
+    get_sampling_keys(scores, order, temperature)
+        .into_iter()
+        .position_max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+}
+
+/// Get the sampling key of each score. Sorting the keys in descending order gives a random order weighted by the scores.
+///
+/// This uses the Gumbel-max trick on the log of the weights to stay accurate with very low temperatures
+fn get_sampling_keys(scores: &[Decimal], order: ScoreOrder, temperature: f64) -> Vec<f64> {
+    let mut rng = rand::thread_rng();
+
+    get_normalized_scores(scores, order)
+        .into_iter()
+        .map(|normalized| {
+            let weight = normalized.max(MIN_WEIGHT);
+            let uniform: f64 = rng.gen_range(f64::EPSILON..1.0);
+            let gumbel = -(-uniform.ln()).ln();
+
+            weight.ln() / temperature + gumbel
+        })
+        .collect_vec()
+}
+
+/// Scale the scores between 0 and 1, where 1 is the best score.
+///
+/// The scores far outside of the others are clamped first, so the placeholder scores given to items without data
+/// don't squash the weights of the others. This uses Tukey's fences on the interquartile range
+fn get_normalized_scores(scores: &[Decimal], order: ScoreOrder) -> Vec<f64> {
+    let values = scores
+        .iter()
+        .map(|score| score.to_f64().unwrap_or_default())
+        .collect_vec();
+    let sorted = values
+        .iter()
+        .copied()
+        .sorted_by(|a, b| a.total_cmp(b))
+        .collect_vec();
+
+    let Some(last) = sorted.len().checked_sub(1) else {
+        return Vec::new();
+    };
+
+    let first_quartile = sorted[last / 4];
+    let third_quartile = sorted[last * 3 / 4];
+    let fence = (third_quartile - first_quartile) * OUTLIER_FENCE;
+    let mut low = sorted[0].max(first_quartile - fence);
+    let mut high = sorted[last].min(third_quartile + fence);
+
+    // All the scores are within the quartiles, so the fences can't tell the outliers apart
+    if high <= low {
+        low = sorted[0];
+        high = sorted[last];
+    }
+
+    let range = high - low;
+    values
+        .into_iter()
+        .map(|value| {
+            if !range.is_finite() || range <= 0.0 {
+                return 1.0;
+            }
+
+            let normalized = (value.clamp(low, high) - low) / range;
+            match order {
+                ScoreOrder::Ascending => 1.0 - normalized,
+                ScoreOrder::Descending => normalized,
+            }
+        })
+        .collect_vec()
+}
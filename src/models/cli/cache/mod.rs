@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use crate::api::clients::create_client;
+use crate::api::listenbrainz::feedback::sync_user_feedback;
 use crate::database::DB_LOCATION;
 use crate::models::config::Config;
 //use crate::models::config::Config;
@@ -61,6 +62,14 @@ pub enum CacheSubcommands {
         username: Option<String>,
     },
 
+    /// Refresh the loved and hated recordings of the user from ListenBrainz
+    ///
+    /// This feedback is used by the radios to remove the hated recordings, and boost the loved ones
+    SyncFeedback {
+        /// Name of the user to refresh the feedback of
+        username: Option<String>,
+    },
+
     RefreshData {
         /// Name of the user to refresh the data
         #[arg(short, long)]
@@ -112,6 +121,12 @@ impl CacheCommand {
                 }
             },
 
+            CacheSubcommands::SyncFeedback { username } => {
+                let username = Config::check_username(username);
+                let count = sync_user_feedback(&username).await?;
+                println!("Saved {count} feedback for {username}");
+            }
+
             CacheSubcommands::RefreshData {
                 username,
                 limit,
@@ -1,10 +1,20 @@
 use core::fmt::Display;
+use core::str::FromStr;
 
 use chrono::DateTime;
+use chrono::Datelike as _;
 use chrono::Duration;
+use chrono::Local;
+use chrono::Months;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
 use chrono::Utc;
+use clap::ArgAction;
+use clap::Args;
 use clap::ValueEnum;
 use derive_more::IsVariant;
+use serde::Deserialize;
+use serde::Serialize;
 
 #[derive(ValueEnum, Clone, Debug, Copy, Default, IsVariant)]
 pub enum SortListensBy {
@@ -71,27 +81,548 @@ pub enum ConfigBool {
     False,
 }
 
-#[derive(ValueEnum, Clone, Debug, Copy, Default, IsVariant)]
+/// A period of time to restrict the listens to.
+///
+/// It can be written as:
+/// - The last days from now: `last30-days`, `last-90-days`, `last 365 days`
+/// - A year, month, or day: `2023`, `2023-06`, `2023-06-21`
+/// - A season: `summer 2022`, `last summer`, `this winter`. Winter starts in december, so `winter 2022` is from december 2022 to february 2023
+/// - A calendar period relative to today: `this year`, `last year`, `this month`, `last month`
+/// - A range between two of the above: `2022-06..2022-09`, `2021..`, `..last year`
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub enum Timeframe {
-    /// Uses the last 30 days from now
-    #[default]
-    Last30Days,
-
-    /// Uses the last 30 days from now    
-    Last90Days,
+    /// The last X days from now
+    LastDays(u32),
 
-    /// Uses the last 365 days from now    
-    Last365Days,
+    /// Between two local dates, inclusive. A missing bound leaves the range open on this side
+    Range {
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    },
 }
 
 impl Timeframe {
-    pub fn get_start_date(&self) -> DateTime<Utc> {
-        let now = Utc::now();
+    /// Create a range from the start of a timeframe to the end of another
+    pub fn between(from: Option<Timeframe>, to: Option<Timeframe>) -> Self {
+        Self::Range {
+            from: from.and_then(|from| from.get_first_day()),
+            to: to.and_then(|to| to.get_last_day()),
+        }
+    }
+
+    pub fn get_start_date(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::LastDays(days) => Some(Utc::now() - Duration::days(*days as i64)),
+            Self::Range { from, .. } => from.map(start_of_day),
+        }
+    }
+
+    pub fn get_end_date(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::LastDays(_) => None,
+            Self::Range { to, .. } => to
+                .and_then(|to| to.succ_opt())
+                .map(|next_day| start_of_day(next_day) - Duration::seconds(1)),
+        }
+    }
+
+    /// Get the same period, one year before
+    pub fn same_period_last_year(&self) -> Self {
+        let year_before = |date: NaiveDate| date.checked_sub_months(Months::new(12));
+
+        match self {
+            Self::LastDays(days) => {
+                let today = Local::now().date_naive();
+                Self::Range {
+                    from: year_before(today - Duration::days(*days as i64)),
+                    to: year_before(today),
+                }
+            }
+            Self::Range { from, to } => Self::Range {
+                from: from.and_then(year_before),
+                to: to.and_then(year_before),
+            },
+        }
+    }
 
+    fn get_first_day(&self) -> Option<NaiveDate> {
         match self {
-            Self::Last30Days => now - Duration::days(30),
-            Self::Last90Days => now - Duration::days(90),
-            Self::Last365Days => now - Duration::days(365),
+            Self::LastDays(days) => Some(Local::now().date_naive() - Duration::days(*days as i64)),
+            Self::Range { from, .. } => *from,
         }
     }
+
+    fn get_last_day(&self) -> Option<NaiveDate> {
+        match self {
+            Self::LastDays(_) => Some(Local::now().date_naive()),
+            Self::Range { to, .. } => *to,
+        }
+    }
+}
+
+impl Default for Timeframe {
+    fn default() -> Self {
+        Self::LastDays(30)
+    }
+}
+
+impl Display for Timeframe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LastDays(days) => write!(f, "last{days}-days"),
+            Self::Range { from, to } => {
+                if let Some(from) = from {
+                    write!(f, "{}", from.format("%Y-%m-%d"))?;
+                }
+                write!(f, "..")?;
+                if let Some(to) = to {
+                    write!(f, "{}", to.format("%Y-%m-%d"))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for Timeframe {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim().to_lowercase();
+        let today = Local::now().date_naive();
+
+        if let Some((from, to)) = value.split_once("..") {
+            let from = parse_optional_period(from, today)?;
+            let to = parse_optional_period(to, today)?;
+
+            return Ok(Self::Range {
+                from: from.map(|(start, _)| start),
+                to: to.map(|(_, end)| end),
+            });
+        }
+
+        if let Some(days) = parse_last_days(&value) {
+            return Ok(Self::LastDays(days));
+        }
+
+        let (from, to) = parse_period(&value, today)?;
+        Ok(Self::Range {
+            from: Some(from),
+            to: Some(to),
+        })
+    }
+}
+
+impl TryFrom<String> for Timeframe {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Timeframe> for String {
+    fn from(value: Timeframe) -> Self {
+        value.to_string()
+    }
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+
+    midnight
+        .and_local_timezone(Local)
+        .earliest()
+        .map(|date| date.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+/// Parse `last30-days`, `last-30-days` or `last 30 days`
+fn parse_last_days(value: &str) -> Option<u32> {
+    let value = value.strip_prefix("last")?.replace('-', " ");
+    let mut words = value.split_whitespace();
+
+    let days = words.next()?.parse().ok()?;
+    match (words.next(), words.next()) {
+        (Some("days" | "day"), None) => Some(days),
+        _ => None,
+    }
+}
+
+fn parse_optional_period(
+    value: &str,
+    today: NaiveDate,
+) -> Result<Option<(NaiveDate, NaiveDate)>, String> {
+    let value = value.trim();
+
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    parse_period(value, today).map(Some)
+}
+
+/// Parse a period of time into its first and last day
+fn parse_period(value: &str, today: NaiveDate) -> Result<(NaiveDate, NaiveDate), String> {
+    let words = value.split_whitespace().collect::<Vec<_>>();
+
+    let period = match words.as_slice() {
+        ["this", "year"] => year_period(today.year()),
+        ["last", "year"] => year_period(today.year() - 1),
+        ["this", "month"] => month_period(today.year(), today.month()),
+        ["last", "month"] => today
+            .checked_sub_months(Months::new(1))
+            .and_then(|date| month_period(date.year(), date.month())),
+        ["this", season] => Season::parse(season).map(|season| season.current(today)),
+        ["last", season] => Season::parse(season).map(|season| season.last(today)),
+        [season, year] | [year, season] if Season::parse(season).is_some() => year
+            .parse()
+            .ok()
+            .and_then(|year| Season::parse(season).and_then(|season| season.of_year(year))),
+        [date] => parse_date_period(date),
+        _ => None,
+    };
+
+    period.ok_or_else(|| format!("Couldn't understand the timeframe `{value}`. Try a date like `2023`, `2023-06` or `2023-06-21`, a season like `last summer`, a range like `2022-06..2022-09`, or `last30-days`"))
+}
+
+/// Parse `2023`, `2023-06`, or `2023-06-21`
+fn parse_date_period(value: &str) -> Option<(NaiveDate, NaiveDate)> {
+    let parts = value.split('-').collect::<Vec<_>>();
+
+    match parts.as_slice() {
+        [year] if year.len() == 4 => year_period(year.parse().ok()?),
+        [year, month] if year.len() == 4 => month_period(year.parse().ok()?, month.parse().ok()?),
+        [_, _, _] => {
+            let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+            Some((date, date))
+        }
+        _ => None,
+    }
+}
+
+fn year_period(year: i32) -> Option<(NaiveDate, NaiveDate)> {
+    Some((
+        NaiveDate::from_ymd_opt(year, 1, 1)?,
+        NaiveDate::from_ymd_opt(year, 12, 31)?,
+    ))
+}
+
+fn month_period(year: i32, month: u32) -> Option<(NaiveDate, NaiveDate)> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let last = first.checked_add_months(Months::new(1))?.pred_opt()?;
+
+    Some((first, last))
+}
+
+/// The meteorological seasons of the northern hemisphere
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "spring" => Some(Self::Spring),
+            "summer" => Some(Self::Summer),
+            "autumn" | "fall" => Some(Self::Autumn),
+            "winter" => Some(Self::Winter),
+            _ => None,
+        }
+    }
+
+    fn first_month(&self) -> u32 {
+        match self {
+            Self::Spring => 3,
+            Self::Summer => 6,
+            Self::Autumn => 9,
+            Self::Winter => 12,
+        }
+    }
+
+    /// Get the season starting in this year
+    fn of_year(&self, year: i32) -> Option<(NaiveDate, NaiveDate)> {
+        let first = NaiveDate::from_ymd_opt(year, self.first_month(), 1)?;
+        let last = first.checked_add_months(Months::new(3))?.pred_opt()?;
+
+        Some((first, last))
+    }
+
+    /// Get the latest season that has already started
+    fn current(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let mut year = today.year();
+
+        loop {
+            match self.of_year(year) {
+                Some((first, last)) if first <= today => return (first, last),
+                _ => year -= 1,
+            }
+        }
+    }
+
+    /// Get the latest season that has already ended
+    fn last(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let mut year = today.year();
+
+        loop {
+            match self.of_year(year) {
+                Some((first, last)) if last < today => return (first, last),
+                _ => year -= 1,
+            }
+        }
+    }
+}
+
+/// Arguments to restrict the listens to a period of time
+#[derive(Args, Debug, Clone, Default, Serialize)]
+pub struct ListenRangeArgs {
+    /// Only use the listens made since the start of this date or timeframe (Ex: "2022-06-21", "2022", "last summer")
+    #[arg(long)]
+    pub from: Option<Timeframe>,
+
+    /// Only use the listens made before the end of this date or timeframe (Ex: "2022-09-21", "2022", "last summer")
+    #[arg(long)]
+    pub to: Option<Timeframe>,
+
+    /// Use the same period, but one year before. If no period is given, the last 30 days are used
+    #[arg(long, action=ArgAction::SetTrue)]
+    pub same_period_last_year: bool,
+}
+
+impl ListenRangeArgs {
+    /// Get the timeframe to use, with `--from` and `--to` overriding the bounds of the given timeframe
+    pub fn get_timeframe(&self, timeframe: Option<Timeframe>) -> Option<Timeframe> {
+        let timeframe = match (self.from, self.to) {
+            (None, None) => timeframe,
+            (from, to) => Some(Timeframe::between(from.or(timeframe), to.or(timeframe))),
+        };
+
+        if self.same_period_last_year {
+            return Some(timeframe.unwrap_or_default().same_period_last_year());
+        }
+
+        timeframe
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::models::cli::common::parse_period;
+    use crate::models::cli::common::ListenRangeArgs;
+    use crate::models::cli::common::Timeframe;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn range(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Timeframe {
+        Timeframe::Range { from, to }
+    }
+
+    fn parse(value: &str) -> Timeframe {
+        value
+            .parse()
+            .unwrap_or_else(|err| panic!("`{value}` should parse: {err}"))
+    }
+
+    #[test]
+    fn parse_last_days() {
+        assert_eq!(parse("last30-days"), Timeframe::LastDays(30));
+        assert_eq!(parse("last-90-days"), Timeframe::LastDays(90));
+        assert_eq!(parse("last 365 days"), Timeframe::LastDays(365));
+        assert_eq!(parse("Last 1 Day"), Timeframe::LastDays(1));
+    }
+
+    #[test]
+    fn parse_dates() {
+        assert_eq!(
+            parse("2023"),
+            range(Some(date(2023, 1, 1)), Some(date(2023, 12, 31)))
+        );
+        assert_eq!(
+            parse("2024-02"),
+            range(Some(date(2024, 2, 1)), Some(date(2024, 2, 29)))
+        );
+        assert_eq!(
+            parse(" 2023-06-21 "),
+            range(Some(date(2023, 6, 21)), Some(date(2023, 6, 21)))
+        );
+    }
+
+    #[test]
+    fn parse_seasons_of_year() {
+        assert_eq!(
+            parse("summer 2022"),
+            range(Some(date(2022, 6, 1)), Some(date(2022, 8, 31)))
+        );
+        assert_eq!(
+            parse("2022 spring"),
+            range(Some(date(2022, 3, 1)), Some(date(2022, 5, 31)))
+        );
+        assert_eq!(
+            parse("fall 2021"),
+            range(Some(date(2021, 9, 1)), Some(date(2021, 11, 30)))
+        );
+        assert_eq!(
+            parse("winter 2023"),
+            range(Some(date(2023, 12, 1)), Some(date(2024, 2, 29)))
+        );
+    }
+
+    #[test]
+    fn parse_relative_periods() {
+        let today = date(2024, 1, 10);
+
+        assert_eq!(
+            parse_period("this year", today),
+            Ok((date(2024, 1, 1), date(2024, 12, 31)))
+        );
+        assert_eq!(
+            parse_period("last year", today),
+            Ok((date(2023, 1, 1), date(2023, 12, 31)))
+        );
+        assert_eq!(
+            parse_period("this month", today),
+            Ok((date(2024, 1, 1), date(2024, 1, 31)))
+        );
+        assert_eq!(
+            parse_period("last month", today),
+            Ok((date(2023, 12, 1), date(2023, 12, 31)))
+        );
+    }
+
+    #[test]
+    fn parse_relative_seasons() {
+        let january = date(2024, 1, 10);
+        assert_eq!(
+            parse_period("this winter", january),
+            Ok((date(2023, 12, 1), date(2024, 2, 29)))
+        );
+        assert_eq!(
+            parse_period("last winter", january),
+            Ok((date(2022, 12, 1), date(2023, 2, 28)))
+        );
+        assert_eq!(
+            parse_period("last summer", january),
+            Ok((date(2023, 6, 1), date(2023, 8, 31)))
+        );
+
+        // The season isn't over on its last day
+        let end_of_summer = date(2024, 8, 31);
+        assert_eq!(
+            parse_period("this summer", end_of_summer),
+            Ok((date(2024, 6, 1), date(2024, 8, 31)))
+        );
+        assert_eq!(
+            parse_period("last summer", end_of_summer),
+            Ok((date(2023, 6, 1), date(2023, 8, 31)))
+        );
+    }
+
+    #[test]
+    fn parse_ranges() {
+        assert_eq!(
+            parse("2022-06..2022-09"),
+            range(Some(date(2022, 6, 1)), Some(date(2022, 9, 30)))
+        );
+        assert_eq!(parse("2021.."), range(Some(date(2021, 1, 1)), None));
+        assert_eq!(parse("..2020"), range(None, Some(date(2020, 12, 31))));
+        assert_eq!(
+            parse("summer 2022 .. winter 2022"),
+            range(Some(date(2022, 6, 1)), Some(date(2023, 2, 28)))
+        );
+    }
+
+    #[test]
+    fn parse_rejected() {
+        for value in [
+            "",
+            "yesterday",
+            "last 30 weeks",
+            "last30",
+            "lastx-days",
+            "23",
+            "2023-13",
+            "2023-02-30",
+            "summer",
+            "summer abc",
+            "next summer",
+            "2022-06..nope",
+        ] {
+            assert!(
+                value.parse::<Timeframe>().is_err(),
+                "`{value}` shouldn't parse"
+            );
+        }
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for timeframe in [
+            Timeframe::LastDays(30),
+            range(Some(date(2022, 6, 1)), Some(date(2022, 9, 30))),
+            range(Some(date(2021, 1, 1)), None),
+            range(None, Some(date(2020, 12, 31))),
+        ] {
+            assert_eq!(parse(&timeframe.to_string()), timeframe);
+        }
+    }
+
+    #[test]
+    fn same_period_last_year() {
+        assert_eq!(
+            range(Some(date(2024, 1, 1)), Some(date(2024, 2, 29))).same_period_last_year(),
+            range(Some(date(2023, 1, 1)), Some(date(2023, 2, 28)))
+        );
+        assert_eq!(
+            range(Some(date(2021, 1, 1)), None).same_period_last_year(),
+            range(Some(date(2020, 1, 1)), None)
+        );
+    }
+
+    #[test]
+    fn listen_range_args() {
+        assert_eq!(ListenRangeArgs::default().get_timeframe(None), None);
+        assert_eq!(
+            ListenRangeArgs::default().get_timeframe(Some(Timeframe::LastDays(7))),
+            Some(Timeframe::LastDays(7))
+        );
+
+        // `--from` and `--to` replace the bounds of the timeframe
+        let args = ListenRangeArgs {
+            from: Some(parse("2022")),
+            to: Some(parse("2023-06")),
+            same_period_last_year: false,
+        };
+        assert_eq!(
+            args.get_timeframe(Some(parse("2020"))),
+            Some(range(Some(date(2022, 1, 1)), Some(date(2023, 6, 30))))
+        );
+
+        let args = ListenRangeArgs {
+            from: None,
+            to: Some(parse("2023-06")),
+            same_period_last_year: false,
+        };
+        assert_eq!(
+            args.get_timeframe(Some(parse("2023"))),
+            Some(range(Some(date(2023, 1, 1)), Some(date(2023, 6, 30))))
+        );
+
+        let args = ListenRangeArgs {
+            same_period_last_year: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            args.get_timeframe(Some(parse("2023"))),
+            Some(range(Some(date(2022, 1, 1)), Some(date(2022, 12, 31))))
+        );
+    }
 }
@@ -0,0 +1,84 @@
+use crate::datastructures::clippy::registry::ClippyLintKind;
+use crate::models::clippy::lint_severity::LintSeverity;
+use crate::models::config::Config;
+use crate::utils::cli::clap_error;
+use clap::Parser;
+use clap::Subcommand;
+use tracing::info;
+
+#[derive(Parser, Debug, Clone)]
+#[command(version, about, long_about = None)]
+pub struct ClippyConfigCli {
+    #[command(subcommand)]
+    subcommand: ClippyConfigSubcommands,
+}
+
+impl ClippyConfigCli {
+    pub async fn run(&self) -> color_eyre::Result<()> {
+        self.subcommand.run().await
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ClippyConfigSubcommands {
+    /// Check the lint when running `musicbrainz clippy`
+    Enable {
+        /// The name of the lint. Use `musicbrainz clippy --list-lints` to see them all
+        lint: String,
+    },
+
+    /// Don't check the lint when running `musicbrainz clippy`, unless explicitly selected with `--only`
+    Disable {
+        /// The name of the lint. Use `musicbrainz clippy --list-lints` to see them all
+        lint: String,
+    },
+
+    /// Replace the severity of the lint
+    Severity {
+        /// The name of the lint. Use `musicbrainz clippy --list-lints` to see them all
+        lint: String,
+
+        /// The new severity. Leave empty to use the lint's own severity
+        severity: Option<LintSeverity>,
+    },
+}
+
+impl ClippyConfigSubcommands {
+    pub async fn run(&self) -> color_eyre::Result<()> {
+        match self {
+            Self::Enable { lint } => {
+                let conf = Config::load()?;
+                conf.write_or_panic()
+                    .clippy
+                    .set_enabled(get_lint_name(lint), true);
+                info!("Enabled `{lint}`");
+            }
+            Self::Disable { lint } => {
+                let conf = Config::load()?;
+                conf.write_or_panic()
+                    .clippy
+                    .set_enabled(get_lint_name(lint), false);
+                info!("Disabled `{lint}`");
+            }
+            Self::Severity { lint, severity } => {
+                let conf = Config::load()?;
+                conf.write_or_panic()
+                    .clippy
+                    .set_severity(get_lint_name(lint), *severity);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Check that the lint exists
+fn get_lint_name(lint: &str) -> &'static str {
+    match ClippyLintKind::from_name(lint) {
+        Some(lint) => lint.get_name(),
+        None => clap_error(
+            format!("Unknown lint `{lint}`. Use `musicbrainz clippy --list-lints` to see the available lints"),
+            clap::error::ErrorKind::InvalidValue,
+        ),
+    }
+}
@@ -1,13 +1,19 @@
 use crate::models::config::config_trait::ConfigFile as _;
 use crate::models::config::recording_timeout::RecordingTimeoutConfig;
 use crate::models::config::Config;
+use crate::tools::timeouts::list_timeouts;
 use crate::utils::cli::read_mbid_from_input;
 use crate::utils::extensions::chrono_ext::DurationExt;
 use chrono::Duration;
 use clap::Parser;
 use clap::Subcommand;
+use clippy_config::ClippyConfigCli;
 use listen_config::ListenConfigCli;
+use rust_decimal::Decimal;
+use std::path::PathBuf;
+use tracing::info;
 
+pub mod clippy_config;
 pub mod listen_config;
 
 #[derive(Parser, Debug, Clone)]
@@ -17,6 +23,33 @@ pub struct ConfigCli {
     pub command: ConfigCommands,
 }
 
+#[derive(Parser, Debug, Clone)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct TimeoutCli {
+    #[command(subcommand)]
+    pub command: Option<TimeoutSubcommands>,
+
+    /// A string containing a MBID of a recording
+    #[arg(required = true)]
+    pub recording: Option<String>,
+
+    /// A duration to timeout for
+    #[arg(required = true)]
+    pub duration: Option<String>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum TimeoutSubcommands {
+    /// List the recordings that are timed out
+    List,
+
+    /// Remove the timeout of a recording
+    Remove {
+        /// A string containing a MBID of a recording
+        recording: String,
+    },
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum ConfigCommands {
     /// Prevent an MSID to appear in the mbid mapper.
@@ -39,23 +72,38 @@ pub enum ConfigCommands {
     },
 
     /// Prevent the recording to appear on radios for a while. If you're burn out of a track and need it gone, use this.
-    Timeout {
-        /// A string containing a MBID of a recording
-        recording: String,
-
-        /// A duration to timeout for
-        duration: String,
-    },
+    ///
+    /// Use `config timeout list` and `config timeout remove` to manage the timeouts.
+    Timeout(TimeoutCli),
 
     /// Configuration targeting listen data
     Listens(ListenConfigCli),
 
+    /// Enable, disable or change the severity of the lints of `musicbrainz clippy`
+    ///
+    /// Lint options can be set in the `clippy.lints.<LINT>.options` field of the config file.
+    /// Ex: `{"labels": ["<LABEL MBID>"]}` for `missing_release_barcode` only checks the releases of those labels
+    Clippy(ClippyConfigCli),
+
     /// Set the default username
     DefaultUser { username: String },
+
+    /// Set the multiplier applied to the radio scores of the recordings you loved on ListenBrainz (Default: 1.2)
+    ///
+    /// Use a value of 1 to disable the boost
+    LovedMultiplier { multiplier: Decimal },
+
+    /// Set how many days the worldwide listen counts are cached before being fetched again (Default: 7)
+    ///
+    /// The cache is used regardless of its age when running with `OFFLINE=true`
+    PopularityCacheDays { days: u32 },
+
+    /// Set the directory containing your music files. This is used by radios exporting to local playlists (Ex: `--output m3u8`)
+    MusicDirectory { path: PathBuf },
 }
 
 impl ConfigCommands {
-    pub async fn run(&self) -> color_eyre::Result<()> {
+    pub async fn run(&self, conn: &mut sqlx::SqliteConnection) -> color_eyre::Result<()> {
         match self {
             Self::SetToken { username, token } => {
                 let conf = Config::load()?;
@@ -63,15 +111,7 @@ impl ConfigCommands {
                     .set_token(username.clone(), token.clone());
             }
 
-            Self::Timeout {
-                recording,
-                duration,
-            } => {
-                let id = read_mbid_from_input(recording).expect("Couldn't parse MBID");
-                let config_guard = RecordingTimeoutConfig::load()?;
-                let mut config = config_guard.write_or_panic();
-                config.set_timeout(&id, Duration::from_human_string(duration)?);
-            }
+            Self::Timeout(val) => val.run(conn).await?,
 
             Self::BlacklistMapperMSID { msid, remove } => {
                 let conf = Config::load()?;
@@ -83,11 +123,56 @@ impl ConfigCommands {
                 }
             }
             Self::Listens(val) => val.run().await?,
+            Self::Clippy(val) => val.run().await?,
 
             Self::DefaultUser { username } => {
                 let conf = Config::load()?;
                 conf.write_or_panic().default_user = Some(username.clone());
             }
+
+            Self::LovedMultiplier { multiplier } => {
+                let conf = Config::load()?;
+                conf.write_or_panic().loved_multiplier = Some(*multiplier);
+            }
+
+            Self::PopularityCacheDays { days } => {
+                let conf = Config::load()?;
+                conf.write_or_panic().popularity_cache_days = Some(*days);
+            }
+
+            Self::MusicDirectory { path } => {
+                let conf = Config::load()?;
+                conf.write_or_panic().music_directory = Some(path.clone());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl TimeoutCli {
+    pub async fn run(&self, conn: &mut sqlx::SqliteConnection) -> color_eyre::Result<()> {
+        match &self.command {
+            Some(TimeoutSubcommands::List) => list_timeouts(conn).await?,
+            Some(TimeoutSubcommands::Remove { recording }) => {
+                let id = read_mbid_from_input(recording).expect("Couldn't parse MBID");
+                let config_guard = RecordingTimeoutConfig::load()?;
+
+                if !config_guard.write_or_panic().remove_timeout(&id) {
+                    info!("The recording isn't timed out");
+                }
+            }
+            None => {
+                // Clap requires both when there's no subcommand
+                let (Some(recording), Some(duration)) = (&self.recording, &self.duration) else {
+                    unreachable!("The recording and duration are required arguments")
+                };
+
+                let id = read_mbid_from_input(recording).expect("Couldn't parse MBID");
+                let config_guard = RecordingTimeoutConfig::load()?;
+                let mut config = config_guard.write_or_panic();
+                config.set_timeout(&id, Duration::from_human_string(duration)?);
+            }
         }
 
         Ok(())
@@ -1,6 +1,7 @@
 use std::io;
 
 use cache::CacheCommand;
+use clap::ArgAction;
 use clap::Command;
 use clap::CommandFactory;
 use clap::Parser;
@@ -10,8 +11,10 @@ use clap_complete::Generator;
 use clap_complete::Shell;
 use clap_verbosity_flag::InfoLevel;
 use clap_verbosity_flag::Verbosity;
+use common::ListenRangeArgs;
 use common::SortSorterBy;
 use common::StatsTarget;
+use common::Timeframe;
 use config::ConfigCli;
 use listens::ListenCommand;
 use lookup::LookupCommand;
@@ -23,6 +26,7 @@ use crate::models::cli::radio::RadioCommand;
 use crate::tools::bumps::bump_command;
 use crate::tools::bumps::bump_down_command;
 use crate::tools::compatibility::compatibility_command;
+use crate::tools::daemon::run_daemon;
 use crate::tools::daily::daily_report;
 use crate::tools::stats::stats_command;
 
@@ -90,8 +94,14 @@ impl Cli {
 /// bump-down is an alias for `bump <RECORDING> <DURATION> 0.9`
 ///
 /// All the bumps are added multiplicatively, so a recording won't disapear. Use the blacklist to remove them.
+///
+/// Bumps only apply to the radios of the user that added them. Use `bump list`, `bump remove` and `bump clear` to manage them.
 #[derive(Parser, Debug, Clone)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct BumpCLI {
+    #[command(subcommand)]
+    pub command: Option<BumpSubcommands>,
+
     /// The recording to bump
     pub recording: Option<String>,
 
@@ -107,6 +117,39 @@ pub struct BumpCLI {
     pub username: Option<String>,
 }
 
+#[derive(Subcommand, Debug, Clone)]
+pub enum BumpSubcommands {
+    /// List the bumps
+    List {
+        /// Only list the bumps of this user
+        #[arg(short, long)]
+        username: Option<String>,
+
+        /// Also list the expired bumps
+        #[arg(long, action = ArgAction::SetTrue)]
+        expired: bool,
+    },
+
+    /// Remove all the bumps of a recording
+    Remove {
+        /// The recording to remove the bumps of
+        recording: String,
+
+        #[arg(short, long)]
+        username: Option<String>,
+    },
+
+    /// Remove all the bumps of the user
+    Clear {
+        /// Only remove the expired bumps. This removes the expired bumps of all the users, unless `--username` is set
+        #[arg(long, action = ArgAction::SetTrue)]
+        expired: bool,
+
+        #[arg(short, long)]
+        username: Option<String>,
+    },
+}
+
 #[derive(Subcommand, Debug, Clone)]
 pub enum Commands {
     Bump(BumpCLI),
@@ -132,6 +175,32 @@ pub enum Commands {
     /// Commands to deal with the app's configuration
     Config(ConfigCli),
 
+    /// Generate the scheduled radios at their set times
+    ///
+    /// The radios are set in the `daemon.schedule` section of the configuration file. Each entry is a radio recipe (See `radio custom`), with the times to generate it at:
+    ///
+    /// ```json
+    /// "daemon": {
+    ///   "schedule": [
+    ///     {
+    ///       "recipe": "/home/user/radios/morning.toml",
+    ///       "at": ["07:30"],
+    ///       "weekdays": ["mon", "tue", "wed", "thu", "fri"],
+    ///       "output": "listenbrainz",
+    ///       "playlist_name": "Morning radio"
+    ///     }
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// Set `replace` or `playlist_name` to update the same playlist each time, instead of creating a new one.
+    /// The daemon keeps the database and clients open between each generation, so only the new listens need to be fetched
+    Daemon {
+        /// Generate all the scheduled radios once, then exit
+        #[arg(long, action=ArgAction::SetTrue)]
+        once: bool,
+    },
+
     /// Daily report
     Daily {
         /// Name of the user to fetch stats listen from
@@ -178,6 +247,15 @@ pub enum Commands {
         /// Sort by:
         #[arg(short, long, default_value_t = SortSorterBy::Count)]
         sort: SortSorterBy,
+
+        /// Only use the listens of this time range.
+        ///
+        /// This accepts presets (`last30-days`), dates (`2023`, `2023-06`), seasons (`last summer`), or ranges (`2022-06..2022-09`)
+        #[arg(short, long)]
+        range: Option<Timeframe>,
+
+        #[command(flatten)]
+        range_args: ListenRangeArgs,
     },
 
     Unstable(UnstableCommand),
@@ -190,12 +268,15 @@ impl Commands {
                 username,
                 target,
                 sort,
+                range,
+                range_args,
             } => {
                 stats_command(
                     conn,
                     &Config::check_username(username).to_lowercase(),
                     *target,
                     *sort,
+                    range_args.get_timeframe(*range),
                 )
                 .await;
             }
@@ -208,7 +289,9 @@ impl Commands {
 
             Self::Cache(val) => val.run(conn).await?,
 
-            Self::Config(val) => val.command.run().await?,
+            Self::Config(val) => val.command.run(conn).await?,
+
+            Self::Daemon { once } => run_daemon(conn, *once).await?,
 
             Self::Daily { username } => daily_report(conn, &Config::check_username(username)).await,
 
@@ -218,7 +301,7 @@ impl Commands {
 
             Self::Mapping(val) => val.run(conn).await?,
 
-            Self::Musicbrainz(val) => val.run(conn).await,
+            Self::Musicbrainz(val) => val.run(conn).await?,
 
             Self::Bump(val) => bump_command(conn, val.clone()).await,
 
@@ -1,8 +1,18 @@
+use std::path::PathBuf;
+
 use clap::Parser;
 use clap::Subcommand;
+use interzic::models::clippy_crawl::ClippyCrawlSettings;
 
+use crate::datastructures::clippy::registry::ClippyLintKind;
+use crate::models::clippy::report::ClippyReportFormat;
+use crate::models::clippy::start::ClippyStart;
+use crate::models::clippy::start::ClippyStartType;
+use crate::models::config::Config;
+use crate::tools::musicbrainz::clippy::get_start_nodes;
 use crate::tools::musicbrainz::clippy::mb_clippy;
-use crate::utils::cli::read_mbid_from_input;
+use crate::tools::musicbrainz::clippy::print_lint_list;
+use crate::utils::cli::clap_error;
 use crate::utils::whitelist_blacklist::WhitelistBlacklist;
 
 #[derive(Parser, Debug, Clone)]
@@ -13,8 +23,8 @@ pub struct MusicbrainzCommand {
 }
 
 impl MusicbrainzCommand {
-    pub async fn run(&self, conn: &mut sqlx::SqliteConnection) {
-        self.subcommand.run(conn).await;
+    pub async fn run(&self, conn: &mut sqlx::SqliteConnection) -> color_eyre::Result<()> {
+        self.subcommand.run(conn).await
     }
 }
 
@@ -24,52 +34,141 @@ pub enum MusicbrainzSubcommands {
     ///
     /// ⚠️ All tips are suggestions. Take them with a grain of salt. If you are unsure, it's preferable to skip.
     Clippy {
-        /// The MBID of a recording to start from
-        start_mbid: Option<String>,
+        /// The MBID or URL of the entity to start from. For `--start-type listens`, this is the username instead (Default: your listens)
+        start: Option<String>,
+
+        /// The type of the entity to start from (Default: guessed from the URL, or `recording`)
+        #[arg(short, long)]
+        start_type: Option<ClippyStartType>,
+
+        /// Only check the entities that are at most this number of relations away from the start
+        #[arg(long)]
+        max_depth: Option<u32>,
+
+        /// Stop after checking this number of entities
+        #[arg(long)]
+        max_count: Option<u64>,
+
+        /// Continue the last unfinished crawl instead of starting a new one. It keeps the `--new-first`, `--max-depth` and `--max-count` it got started with
+        #[arg(long, conflicts_with_all = ["start", "start_type", "max_depth", "max_count", "new_first"])]
+        resume: bool,
 
         /// Whether to check FILO (first in, last out) instead of FIFO (first in, first out)
         #[arg(short, long)]
         new_first: bool,
 
-        /// List of lints that should only be checked (Note: Put this argument last or before another argument)
-        #[arg(short, long, num_args = 0..)]
-        whitelist: Option<Vec<String>>,
+        /// Only check those lints, even if they are disabled in the config. Separate multiple lints with commas
+        #[arg(short = 'w', long, visible_alias = "whitelist", value_delimiter = ',')]
+        only: Vec<String>,
+
+        /// Don't check those lints. Separate multiple lints with commas
+        #[arg(
+            short = 'b',
+            long,
+            visible_alias = "blacklist",
+            value_delimiter = ',',
+            conflicts_with = "only"
+        )]
+        skip: Vec<String>,
+
+        /// List all the lints with their description, and whether they are enabled
+        ///
+        /// Lints can be enabled, disabled and configured with `config clippy`, or in the `clippy` section of the config file
+        #[arg(long, exclusive = true)]
+        list_lints: bool,
+
+        /// Check without stopping at each lint, and write all of them to a report file instead
+        #[arg(long)]
+        report: Option<ClippyReportFormat>,
 
-        /// List of lints that should not be checked (Note: Put this argument last or before another argument)
-        #[arg(short, long, num_args = 0..)]
-        blacklist: Option<Vec<String>>,
+        /// The file to write the report to (Default: `clippy_report.<FORMAT EXTENSION>`)
+        #[arg(long)]
+        report_file: Option<PathBuf>,
     },
 }
 
 impl MusicbrainzSubcommands {
-    pub async fn run(&self, conn: &mut sqlx::SqliteConnection) {
+    pub async fn run(&self, conn: &mut sqlx::SqliteConnection) -> color_eyre::Result<()> {
         match self {
             Self::Clippy {
-                start_mbid,
+                start,
+                start_type,
+                max_depth,
+                max_count,
+                resume,
                 new_first,
-                whitelist,
-                blacklist,
+                only,
+                skip,
+                list_lints,
+                report,
+                report_file,
             } => {
-                let mbid = start_mbid
-                    .clone()
-                    .unwrap_or_else(|| "8f3471b5-7e6a-48da-86a9-c1c07a0f47ae".to_string());
-
-                let filter = if let Some(whitelist) = whitelist {
-                    WhitelistBlacklist::WhiteList(whitelist.clone())
-                } else if let Some(blacklist) = blacklist {
-                    WhitelistBlacklist::BlackList(blacklist.clone())
+                if *list_lints {
+                    print_lint_list();
+                    return Ok(());
+                }
+
+                for name in only.iter().chain(skip) {
+                    if ClippyLintKind::from_name(name).is_none() {
+                        clap_error(
+                            format!("Unknown lint `{name}`. Use `--list-lints` to see the available lints"),
+                            clap::error::ErrorKind::InvalidValue,
+                        );
+                    }
+                }
+
+                let filter = if !only.is_empty() {
+                    WhitelistBlacklist::WhiteList(only.clone())
                 } else {
-                    WhitelistBlacklist::BlackList(Vec::new())
+                    WhitelistBlacklist::BlackList(skip.clone())
                 };
 
-                mb_clippy(
-                    conn,
-                    &read_mbid_from_input(&mbid).expect("Couldn't read mbid"),
-                    *new_first,
-                    &filter,
-                )
-                .await;
+                // Explicitly selected lints are checked even if disabled in the config.
+                // Their options are read now, so invalid options are caught before starting to crawl
+                let config = Config::load_or_panic().read_or_panic().clippy.clone();
+                let lints = ClippyLintKind::ALL
+                    .into_iter()
+                    .filter(|lint| {
+                        let name = lint.get_name().to_string();
+                        filter.is_allowed(&name) && (!only.is_empty() || config.is_enabled(&name))
+                    })
+                    .map(|lint| lint.get_checker(&config))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let start = if *resume {
+                    ClippyStart::Resume
+                } else {
+                    let (start_type, start) = match start {
+                        Some(start) => (
+                            start_type
+                                .or_else(|| ClippyStartType::from_url(start))
+                                .unwrap_or(ClippyStartType::Recording),
+                            start.clone(),
+                        ),
+                        None => (ClippyStartType::Listens, Config::check_username(&None)),
+                    };
+
+                    ClippyStart::Entities {
+                        nodes: get_start_nodes(conn, start_type, &start).await?,
+                        settings: ClippyCrawlSettings {
+                            new_first: *new_first,
+                            max_depth: max_depth.map(i64::from),
+                            max_count: max_count.map(|max| max as i64),
+                        },
+                    }
+                };
+
+                let report_output = report.map(|format| {
+                    let path = report_file.clone().unwrap_or_else(|| {
+                        PathBuf::from(format!("clippy_report.{}", format.get_extension()))
+                    });
+                    (format, path)
+                });
+
+                mb_clippy(conn, start, &lints, report_output).await;
             }
         }
+
+        Ok(())
     }
 }
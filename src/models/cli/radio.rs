@@ -1,26 +1,45 @@
 use core::fmt::Display;
 use std::ops::Deref;
+use std::path::PathBuf;
 
 use clap::ArgAction;
 use clap::ValueEnum;
 use clap::{Parser, Subcommand};
+use derive_more::IsVariant;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::datastructures::radio::collector::RadioCollector;
-use crate::datastructures::radio::collector::RadioCollectorBuilder;
+use crate::datastructures::radio::collector::RadioCollectorOptions;
+use crate::datastructures::radio::exporter::RadioExporter;
+use crate::datastructures::radio::exporter::RadioExporterBuilder;
+use crate::datastructures::radio::recipe::RadioRecipe;
+use crate::datastructures::radio::recipe::RecipeSeeder;
+use crate::datastructures::radio::seeders::entity::EntitySeeder;
 use crate::datastructures::radio::seeders::listens::ListenSeeder;
 use crate::datastructures::radio::seeders::listens::ListenSeederBuilder;
 use crate::datastructures::radio::seeders::SeederSettings;
 use crate::datastructures::radio::seeders::SeederSettingsBuilder;
+use crate::datastructures::radio::sorters::album::AlbumSorter;
+use crate::datastructures::radio::sorters::RadioSorters;
 use crate::models::config::Config;
+use crate::tools::radio::albums::album_radio;
 use crate::tools::radio::circles::create_radio_mix;
+use crate::tools::radio::custom::custom_radio;
+use crate::tools::radio::discovery::discovery_radio;
+use crate::tools::radio::get_listen_filters;
+use crate::tools::radio::group::group_radio;
+use crate::tools::radio::history::export_radio_history;
+use crate::tools::radio::history::list_radio_history;
 use crate::tools::radio::listen_rate::listen_rate_radio;
 use crate::tools::radio::overdue::overdue_radio;
 use crate::tools::radio::shared::shared_radio;
 use crate::tools::radio::underrated::underrated_mix;
 
+use super::common::ListenRangeArgs;
 use super::common::Timeframe;
 
-#[derive(Parser, Debug, Clone)]
+#[derive(Parser, Debug, Clone, Serialize)]
 #[command(version, about, long_about = None)]
 pub struct RadioCommand {
     #[command(subcommand)]
@@ -31,70 +50,303 @@ pub struct RadioCommand {
     min_count: Option<u64>,
 
     /// The minimum duration the playlist should last for. This accept natural language (Ex: "1 hour 36 mins")
-    #[arg(long)]
+    #[arg(long, value_parser = validate_duration)]
     min_duration: Option<String>,
 
-    /// For radios based on listens, what time range of listens to use as reference
+    /// The maximum number of tracks of the same artist in the playlist
+    #[arg(long)]
+    max_per_artist: Option<u64>,
+
+    /// The maximum number of tracks of the same release group in the playlist
+    #[arg(long)]
+    max_per_release_group: Option<u64>,
+
+    /// The minimum number of tracks between two tracks of the same artist. Tracks that are too close are pushed further in the playlist
+    #[arg(long)]
+    artist_spacing: Option<u64>,
+
+    /// The maximum duration of the playlist. This accept natural language (Ex: "2 hours")
+    #[arg(long, value_parser = validate_duration)]
+    max_duration: Option<String>,
+
+    /// Reorder the playlist so that similar tracks follow each other, using their MusicBrainz tags and genres.
+    ///
+    /// This may undo the spacing between tracks of the same artist. Album radios keep the order of their tracklists
+    #[arg(long, action=ArgAction::SetTrue)]
+    smooth_transitions: bool,
+
+    /// When used with `--smooth-transitions`, a CSV file giving the BPM and key of recordings. It needs a `recording_mbid` column, and `bpm` and/or `key` columns (Ex: `A minor`, `F#m`)
+    #[arg(long)]
+    transition_features: Option<PathBuf>,
+
+    /// For radios based on listens, what time range of listens to use as reference.
+    ///
+    /// This accepts presets (`last30-days`), dates (`2023`, `2023-06`), seasons (`last summer`), or ranges (`2022-06..2022-09`)
     #[arg(long)]
     seed_listen_range: Option<Timeframe>,
 
+    #[command(flatten)]
+    seed_range: ListenRangeArgs,
+
     /// When used with `seed_listen_range`, how many listens should be given as a minimum, even if they are outside of the range (Default: 3)
     #[arg(long)]
     min_seed_listens: Option<u64>,
 
+    /// Only use the recordings of this artist (MBID, URL, or name)
+    #[arg(long)]
+    seed_artist: Option<String>,
+
+    /// Only use the recordings of this release (MBID, URL, or name)
+    #[arg(long)]
+    seed_release: Option<String>,
+
+    /// Only use the recordings of this release group (MBID, URL, or name)
+    #[arg(long)]
+    seed_release_group: Option<String>,
+
+    /// Only use the recordings of this recording or release collection (MBID or URL)
+    #[arg(long)]
+    seed_collection: Option<String>,
+
+    /// Only use the recordings tagged with this tag or genre
+    #[arg(long)]
+    seed_tag: Option<String>,
+
+    /// Only use the recordings of this playlist. Takes a ListenBrainz playlist (MBID or URL), or the path to a JSPF file. Private playlists are read with the user's token
+    #[arg(long)]
+    seed_playlist: Option<String>,
+
+    /// Instead of strictly following the scores, randomly pick the recordings using their score as weight.
+    ///
+    /// This keeps the radios fresh, while still favoring the best scores. Only used by score based radios (rate, overdue, underrated, custom)
+    #[arg(long, action=ArgAction::SetTrue)]
+    shuffle_weighted: bool,
+
+    /// When used with `--shuffle-weighted`, how much the scores matter. Lower values get closer to the strict ordering, higher values get closer to a random shuffle
+    #[arg(long, default_value_t = 1.0, value_parser = parse_temperature)]
+    temperature: f64,
+
     /// Where to output the radio
     #[arg(short, long, default_value_t = RadioExportTarget::Listenbrainz)]
     output: RadioExportTarget,
+
+    /// The file to write the playlist to. Required when exporting to a file (Ex: `--output jspf`)
+    #[arg(long)]
+    output_file: Option<PathBuf>,
+
+    /// Replace the tracks of an existing playlist instead of creating a new one. Takes the playlist's id or URL (Only for ListenBrainz and YouTube)
+    #[arg(long)]
+    replace: Option<String>,
+
+    /// The name of the playlist. If you already have a playlist with this name, it gets replaced instead of creating a new one (Only for ListenBrainz and YouTube)
+    #[arg(long)]
+    playlist_name: Option<String>,
+
+    /// Don't export the playlist. Instead, print its tracks with the data used to pick them (Seed listens, filters, and sorting scores)
+    #[arg(long, action=ArgAction::SetTrue)]
+    dry_run: bool,
+
+    /// Explain why this recording is or isn't in the playlist (MBID or URL)
+    #[arg(long)]
+    explain: Option<String>,
+
+    /// Keep the recordings you hated on ListenBrainz. By default, they are removed from the radios based on listens.
+    ///
+    /// The feedback is read from the cache. Use `cache sync-feedback` to refresh it
+    #[arg(long, action=ArgAction::SetTrue)]
+    keep_hated: bool,
 }
 
 impl RadioCommand {
     pub fn get_collector(&self) -> RadioCollector {
-        let collector = RadioCollectorBuilder::default();
+        RadioCollectorOptions {
+            min_count: self.min_count,
+            min_duration: self.min_duration.clone(),
+            max_duration: self.max_duration.clone(),
+            max_per_artist: self.max_per_artist,
+            max_per_release_group: self.max_per_release_group,
+            artist_spacing: self.artist_spacing,
+            smooth_transitions: self.smooth_transitions,
+            transition_features: self.transition_features.clone(),
+        }
+        .build()
+        .expect("The durations are validated when parsing the arguments")
+    }
 
-        let collector = match self.min_count {
-            Some(val) => collector.count(val),
-            None => collector.count_none(),
+    pub fn get_exporter(&self) -> RadioExporter {
+        let exporter = RadioExporterBuilder::default()
+            .target(self.output.clone())
+            .dry_run(self.dry_run)
+            .report_none();
+
+        let exporter = match self.explain.as_ref() {
+            Some(val) => exporter.explain(val.clone()),
+            None => exporter.explain_none(),
         };
 
-        let collector = match self.min_duration.as_ref() {
-            Some(val) => {
-                let dura: humantime::Duration = val
-                    .clone()
-                    .parse()
-                    .expect("Couldn't parse mimimum lenght for radio");
-                let std_dura = dura.deref();
-                let chrono_dura = chrono::Duration::from_std(*std_dura).unwrap();
-                collector.duration(chrono_dura)
-            }
-            None => collector.duration_none(),
+        let exporter = match self.output_file.as_ref() {
+            Some(val) => exporter.output_file(val.clone()),
+            None => exporter.output_file_none(),
         };
 
-        collector.build()
+        let exporter = match self.replace.as_ref() {
+            Some(val) => exporter.replace(val.clone()),
+            None => exporter.replace_none(),
+        };
+
+        let exporter = match self.playlist_name.as_ref() {
+            Some(val) => exporter.playlist_name(val.clone()),
+            None => exporter.playlist_name_none(),
+        };
+
+        // Save the generated radios in the history. Re-exports from the history aren't saved again
+        let exporter = match self.command.get_name() {
+            Some(name) => exporter.radio(name.to_string()).parameters(
+                serde_json::to_string(self).expect("Couldn't serialize the radio parameters"),
+            ),
+            None => exporter.radio_none().parameters_none(),
+        };
+
+        exporter.build()
+    }
+
+    /// Get the temperature of the weighted shuffle, if enabled
+    fn get_shuffle_temperature(&self) -> Option<f64> {
+        self.shuffle_weighted.then_some(self.temperature)
+    }
+
+    /// Get the token of the user. This is only required when exporting to listenbrainz, and not on dry runs
+    fn get_token(&self, username: &Option<String>, token: &Option<String>) -> Option<String> {
+        if !self.output.is_listenbrainz() || self.dry_run {
+            return token.clone();
+        }
+
+        Some(Config::check_token(
+            &Config::check_username(username),
+            token,
+        ))
     }
 
     fn get_seeder_settings(&self) -> SeederSettings {
+        let range = self.seed_range.get_timeframe(self.seed_listen_range);
+
         SeederSettingsBuilder::default()
             .min_listen_per_recording(self.min_seed_listens.unwrap_or(3))
-            .min_listened_at(self.seed_listen_range.map(|r| r.get_start_date()))
-            .max_listened_at_default()
+            .min_listened_at(range.and_then(|r| r.get_start_date()))
+            .max_listened_at(range.and_then(|r| r.get_end_date()))
             .build()
     }
 
-    fn get_listen_seeder(&self, username: &Option<String>) -> ListenSeeder {
+    /// Get the entities the seeded recordings are restricted to. Only recordings the user listened to are kept
+    fn get_entity_seeders(&self) -> Vec<EntitySeeder> {
+        [
+            self.seed_artist.clone().map(EntitySeeder::Artist),
+            self.seed_release.clone().map(EntitySeeder::Release),
+            self.seed_release_group
+                .clone()
+                .map(EntitySeeder::ReleaseGroup),
+            self.seed_collection.clone().map(EntitySeeder::Collection),
+            self.seed_tag.clone().map(EntitySeeder::Tag),
+            self.seed_playlist.clone().map(EntitySeeder::Playlist),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Get the token used to seed the radio. Unlike [`Self::get_token`], this is optional and only used to read private playlists
+    fn get_seeding_token(username: &str, token: &Option<String>) -> Option<String> {
+        token.clone().or_else(|| Config::get_saved_token(username))
+    }
+
+    fn get_listen_seeder(&self, username: &Option<String>, token: &Option<String>) -> ListenSeeder {
+        let username = Config::check_username(username);
+        let token = Self::get_seeding_token(&username, token);
+
         ListenSeederBuilder::default()
-            .username(Config::check_username(username))
+            .username(username)
             .settings(self.get_seeder_settings())
+            .restrict_to(self.get_entity_seeders())
+            .token(token)
             .build()
     }
 
+    fn get_recipe_seeder(&self, seeder: &RecipeSeeder, token: &Option<String>) -> ListenSeeder {
+        match seeder {
+            RecipeSeeder::Listens {
+                username,
+                seed_listen_range,
+                min_seed_listens,
+                restrict_to,
+            } => {
+                let range = self
+                    .seed_range
+                    .get_timeframe(seed_listen_range.or(self.seed_listen_range));
+
+                let settings = SeederSettingsBuilder::default()
+                    .min_listen_per_recording(
+                        min_seed_listens.or(self.min_seed_listens).unwrap_or(3),
+                    )
+                    .min_listened_at(range.and_then(|r| r.get_start_date()))
+                    .max_listened_at(range.and_then(|r| r.get_end_date()))
+                    .build();
+
+                let username = Config::check_username(username);
+                let token = Self::get_seeding_token(&username, token);
+
+                ListenSeederBuilder::default()
+                    .username(username)
+                    .settings(settings)
+                    .restrict_to(
+                        restrict_to
+                            .iter()
+                            .cloned()
+                            .chain(self.get_entity_seeders())
+                            .collect::<Vec<_>>(),
+                    )
+                    .token(token)
+                    .build()
+            }
+        }
+    }
+
     pub async fn run(&self, conn: &mut sqlx::SqliteConnection) -> color_eyre::Result<()> {
         self.command
-            .run(conn, self.get_collector(), self, self.output.clone())
+            .run(conn, self.get_collector(), self, self.get_exporter())
             .await
     }
 }
 
-#[derive(Subcommand, Debug, Clone)]
+pub fn parse_duration(value: &str) -> Result<chrono::Duration, crate::Error> {
+    let dura: humantime::Duration = value
+        .parse()
+        .map_err(|_| crate::Error::DurationParseError(value.to_string()))?;
+    chrono::Duration::from_std(*dura.deref())
+        .map_err(|_| crate::Error::DurationParseError(value.to_string()))
+}
+
+/// Check that a duration argument can be parsed, while keeping it as written
+fn validate_duration(value: &str) -> Result<String, String> {
+    parse_duration(value)
+        .map(|_| value.to_string())
+        .map_err(|err| err.to_string())
+}
+
+/// Parse the temperature of the weighted shuffle, which needs to be above zero
+fn parse_temperature(value: &str) -> Result<f64, String> {
+    let temperature: f64 = value
+        .parse()
+        .map_err(|_| format!("`{value}` isn't a number"))?;
+
+    if temperature <= 0.0 || !temperature.is_finite() {
+        return Err("The temperature must be above 0".to_string());
+    }
+
+    Ok(temperature)
+}
+
+#[derive(Subcommand, Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RadioSubcommands {
     /// Randomly adds recordings from artists you already listened to
     Circles {
@@ -105,6 +357,7 @@ pub enum RadioSubcommands {
         ///
         /// You can find it at <https://listenbrainz.org/settings/>.
         /// If it's set in the config file, you can ignore this argument
+        #[serde(skip)]
         token: Option<String>,
 
         /// Use this flag to only get unlistened recordings. This is great for exploration playlists
@@ -117,7 +370,7 @@ pub enum RadioSubcommands {
     /// This radio will create a playlist containing all the tracks that you listen to, but seemingly no one else does.
     ///
     ///> The mix is made by calculating a score for each listen. This score is composed of two values:
-    ///> - The rank in the user's top 1000 recording of all time (First place get 100 points, second get 999.9, etc...)
+    ///> - The rank in the user's recordings of all time, scaled over all of them (First place get 100 points, last place get 0)
     ///> - The percentage of the recording's listens being from the user (Made with this formula: (user listens / worldwide listens) *100)
     Underrated {
         /// Name of the user to fetch listens from
@@ -127,6 +380,7 @@ pub enum RadioSubcommands {
         /// You can find it at <https://listenbrainz.org/settings/>.
         /// If it's set in the config file, you can ignore this argument
         #[arg(short, long)]
+        #[serde(skip)]
         token: Option<String>,
     },
 
@@ -143,6 +397,7 @@ pub enum RadioSubcommands {
         /// You can find it at <https://listenbrainz.org/settings/>.
         /// If it's set in the config file, you can ignore this argument
         #[arg(short, long)]
+        #[serde(skip)]
         token: Option<String>,
 
         /// Minimum listen count
@@ -152,6 +407,10 @@ pub enum RadioSubcommands {
         /// The amount of hours needed to wait after a recording have been given before it is re-suggested
         #[arg(short, long, default_value_t = 0)]
         cooldown: u64,
+
+        /// The amount of days needed to wait after a recording have been put in a radio before it is re-suggested
+        #[arg(long)]
+        suggestion_cooldown: Option<u64>,
     },
 
     /// Generate playlists based on recording that the user should have listened to by now
@@ -167,6 +426,7 @@ pub enum RadioSubcommands {
         /// You can find it at <https://listenbrainz.org/settings/>.
         /// If it's set in the config file, you can ignore this argument
         #[arg(short, long)]
+        #[serde(skip)]
         token: Option<String>,
 
         /// Minimum listen count
@@ -177,6 +437,10 @@ pub enum RadioSubcommands {
         #[arg(short, long, default_value_t = 0)]
         cooldown: u64,
 
+        /// The amount of days needed to wait after a recording have been put in a radio before it is re-suggested
+        #[arg(long)]
+        suggestion_cooldown: Option<u64>,
+
         /// Sort the recordings by the time overdue / the average time between listens
         ///
         /// Instead of sorting by date, the listens are sorted by how many estimated listens should have happened by now (Time elapsed since last listen / Average time per listens)
@@ -190,6 +454,37 @@ pub enum RadioSubcommands {
         at_listening_time: bool,
     },
 
+    /// Generate playlists of whole albums
+    ///
+    /// Instead of individual recordings, the scores are calculated on the listens of the albums (release groups).
+    /// The playlist then contains the full tracklist of the albums, in order, using the release you listened to the most.
+    ///
+    /// `--min-count` and `--min-duration` count whole albums, so the playlist may be a bit longer. The artist and release group constraints aren't used
+    Albums {
+        /// Name of the user to fetch listens from
+        username: Option<String>,
+
+        /// Your user token.
+        ///
+        /// You can find it at <https://listenbrainz.org/settings/>.
+        /// If it's set in the config file, you can ignore this argument
+        #[arg(short, long)]
+        #[serde(skip)]
+        token: Option<String>,
+
+        /// How to sort the albums
+        #[arg(short, long, default_value_t = AlbumSorter::Overdue)]
+        sorter: AlbumSorter,
+
+        /// Minimum listen count of the album
+        #[arg(long)]
+        min: Option<u64>,
+
+        /// The amount of hours needed to wait after an album have been listened before it is re-suggested
+        #[arg(short, long, default_value_t = 0)]
+        cooldown: u64,
+    },
+
     /// Generate playlists based on the listened recordings of two users
     Shared {
         username_a: String,
@@ -201,6 +496,7 @@ pub enum RadioSubcommands {
         /// You can find it at <https://listenbrainz.org/settings/>.
         /// If it's set in the config file, you can ignore this argument
         #[arg(short, long)]
+        #[serde(skip)]
         token: Option<String>,
 
         /// Minimum listen count
@@ -211,15 +507,163 @@ pub enum RadioSubcommands {
         #[arg(short, long, default_value_t = 0)]
         cooldown: u64,
     },
+
+    /// Generate playlists based on the listened recordings of a group of users
+    ///
+    /// The recordings are sorted by the average affinity of the users of the group. The affinity is based on the rank of the recording in each user's listens,
+    /// so a single user with a lot of listens doesn't dominate the playlist.
+    ///
+    /// The playlist is sent to the account of the first user
+    Group {
+        /// Names of the users to fetch listens from
+        #[arg(required = true, num_args = 1..)]
+        usernames: Vec<String>,
+
+        /// Your user token.
+        ///
+        /// You can find it at <https://listenbrainz.org/settings/>.
+        /// If it's set in the config file, you can ignore this argument
+        #[arg(short, long)]
+        #[serde(skip)]
+        token: Option<String>,
+
+        /// Which recordings of the users to keep
+        #[arg(short, long, default_value_t = GroupMode::Intersection)]
+        mode: GroupMode,
+
+        /// With `--mode at-least`, the minimum number of users that need to have listened to a recording
+        #[arg(short = 'k', long, default_value_t = 2)]
+        min_users: usize,
+
+        /// Minimum listen count per user
+        #[arg(long)]
+        min: Option<u64>,
+
+        /// The amount of hours needed to wait after a recording have been given before it is re-suggested
+        #[arg(short, long, default_value_t = 0)]
+        cooldown: u64,
+    },
+
+    /// Generate playlists of the recordings you usually listen to at this time
+    ///
+    /// This radio looks at the hours and weekdays at which you listened to each recording,
+    /// and put first the ones matching the current time. Run it in the morning to get a morning mix, or on friday evening for a friday evening mix.
+    TimeOfDay {
+        /// Name of the user to fetch listens from
+        username: Option<String>,
+
+        /// Your user token.
+        ///
+        /// You can find it at <https://listenbrainz.org/settings/>.
+        /// If it's set in the config file, you can ignore this argument
+        #[arg(short, long)]
+        #[serde(skip)]
+        token: Option<String>,
+
+        /// Minimum listen count
+        #[arg(long)]
+        min: Option<u64>,
+
+        /// The amount of hours needed to wait after a recording have been given before it is re-suggested
+        #[arg(short, long, default_value_t = 0)]
+        cooldown: u64,
+
+        /// Only match the hour of the day, and not the day of the week
+        #[arg(long, action=ArgAction::SetTrue)]
+        ignore_weekday: bool,
+    },
+
+    /// Discover artists related to the ones you listen to
+    ///
+    /// This radio walks the MusicBrainz relationships of your top artists and recordings (band members, collaborations, remixers, producers),
+    /// and suggests recordings of the related artists that you never listened to.
+    /// The more you listen to an artist, and the stronger the relationship is, the more its related artists will appear.
+    Discovery {
+        /// Name of the user to fetch listens from
+        username: Option<String>,
+
+        /// Your user token.
+        ///
+        /// You can find it at <https://listenbrainz.org/settings/>.
+        /// If it's set in the config file, you can ignore this argument
+        #[arg(short, long)]
+        #[serde(skip)]
+        token: Option<String>,
+    },
+
+    /// Generate a playlist from a radio recipe file
+    ///
+    /// A recipe is a TOML file describing where to get the recordings from (`seeder`), which `filters` to apply in order, and how to sort them (`sorter`).
+    ///
+    /// Example:
+    ///
+    /// ```toml
+    /// name = "Forgotten favorites"
+    ///
+    /// [seeder]
+    /// type = "listens"
+    /// seed_listen_range = "last365-days"
+    ///
+    /// [[filters]]
+    /// type = "min_listens"
+    /// value = 5
+    ///
+    /// [[filters]]
+    /// type = "cooldown"
+    /// value = 48
+    ///
+    /// [[filters]]
+    /// type = "timeouts"
+    ///
+    /// [sorter]
+    /// type = "overdue_factor"
+    /// at_listening_time = false
+    /// ```
+    ///
+    /// Available filters: `min_listens`, `cooldown` (in hours), `timeouts`, `recently_suggested` (in days), `hated`
+    ///
+    /// Available sorters: `listen_rate`, `overdue`, `overdue_factor`, `underrated`, `time_of_day`
+    Custom {
+        /// Path to the recipe file
+        recipe: PathBuf,
+
+        /// Your user token.
+        ///
+        /// You can find it at <https://listenbrainz.org/settings/>.
+        /// If it's set in the config file, you can ignore this argument
+        #[arg(short, long)]
+        #[serde(skip)]
+        token: Option<String>,
+    },
+
+    /// See and re-export the previously generated radios
+    History(RadioHistoryCli),
 }
 
 impl RadioSubcommands {
+    /// Get the name of the radio, or none if the command doesn't generate a radio
+    pub fn get_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Circles { .. } => Some("circles"),
+            Self::Underrated { .. } => Some("underrated"),
+            Self::Rate { .. } => Some("rate"),
+            Self::Overdue { .. } => Some("overdue"),
+            Self::Albums { .. } => Some("albums"),
+            Self::Shared { .. } => Some("shared"),
+            Self::Group { .. } => Some("group"),
+            Self::TimeOfDay { .. } => Some("time_of_day"),
+            Self::Discovery { .. } => Some("discovery"),
+            Self::Custom { .. } => Some("custom"),
+            Self::History(_) => None,
+        }
+    }
+
     pub async fn run(
         &self,
         conn: &mut sqlx::SqliteConnection,
         collector: RadioCollector,
         command: &RadioCommand,
-        target: RadioExportTarget,
+        exporter: RadioExporter,
     ) -> color_eyre::Result<()> {
         match self {
             Self::Circles {
@@ -230,11 +674,11 @@ impl RadioSubcommands {
             } => {
                 create_radio_mix(
                     conn,
-                    command.get_listen_seeder(username),
-                    Config::check_token(&Config::check_username(username), token),
+                    command.get_listen_seeder(username, token),
+                    command.get_token(username, token),
                     *unlistened,
                     collector,
-                    target,
+                    exporter,
                 )
                 .await;
             }
@@ -242,10 +686,12 @@ impl RadioSubcommands {
             Self::Underrated { username, token } => {
                 underrated_mix(
                     conn,
-                    command.get_listen_seeder(username),
+                    command.get_listen_seeder(username, token),
                     collector,
-                    &Config::check_token(&Config::check_username(username), token),
-                    target,
+                    command.get_token(username, token).as_deref(),
+                    exporter,
+                    command.get_shuffle_temperature(),
+                    command.keep_hated,
                 )
                 .await?;
             }
@@ -254,15 +700,19 @@ impl RadioSubcommands {
                 token,
                 min,
                 cooldown,
+                suggestion_cooldown,
             } => {
                 listen_rate_radio(
                     conn,
-                    command.get_listen_seeder(username),
-                    &Config::check_token(&Config::check_username(username), token),
+                    command.get_listen_seeder(username, token),
+                    command.get_token(username, token).as_deref(),
                     *min,
                     *cooldown,
+                    *suggestion_cooldown,
+                    command.keep_hated,
                     collector,
-                    target,
+                    exporter,
+                    command.get_shuffle_temperature(),
                 )
                 .await?;
             }
@@ -272,19 +722,44 @@ impl RadioSubcommands {
                 token,
                 min,
                 cooldown,
+                suggestion_cooldown,
                 overdue_factor: delay_factor,
                 at_listening_time,
             } => {
                 overdue_radio(
                     conn,
-                    command.get_listen_seeder(username),
-                    &Config::check_token(&Config::check_username(username), token),
+                    command.get_listen_seeder(username, token),
+                    command.get_token(username, token).as_deref(),
                     *min,
                     *cooldown,
+                    *suggestion_cooldown,
+                    command.keep_hated,
                     *delay_factor,
                     command.get_collector(),
                     *at_listening_time,
-                    target,
+                    exporter,
+                    command.get_shuffle_temperature(),
+                )
+                .await?;
+            }
+
+            Self::Albums {
+                username,
+                token,
+                sorter,
+                min,
+                cooldown,
+            } => {
+                album_radio(
+                    conn,
+                    command.get_listen_seeder(username, token),
+                    command.get_token(username, token).as_deref(),
+                    *sorter,
+                    *min,
+                    *cooldown,
+                    collector,
+                    exporter,
+                    command.get_shuffle_temperature(),
                 )
                 .await?;
             }
@@ -298,27 +773,209 @@ impl RadioSubcommands {
             } => {
                 shared_radio(
                     conn,
-                    command.get_listen_seeder(&Some(username_a.to_string())),
+                    command.get_listen_seeder(&Some(username_a.to_string()), token),
                     username_b.to_string(),
                     *min,
                     *cooldown,
                     command.get_collector(),
-                    &Config::check_token(&Config::check_username(&None), token),
-                    target,
+                    command.get_token(&None, token).as_deref(),
+                    exporter,
+                    command.keep_hated,
+                )
+                .await?;
+            }
+
+            Self::Group {
+                usernames,
+                token,
+                mode,
+                min_users,
+                min,
+                cooldown,
+            } => {
+                let owner = usernames.first().cloned();
+
+                group_radio(
+                    conn,
+                    command.get_listen_seeder(&owner, token),
+                    usernames.clone(),
+                    mode.get_min_users(*min_users, usernames.len()),
+                    *min,
+                    *cooldown,
+                    collector,
+                    command.get_token(&owner, token).as_deref(),
+                    exporter,
+                    command.get_shuffle_temperature(),
+                    command.keep_hated,
+                )
+                .await?;
+            }
+
+            Self::TimeOfDay {
+                username,
+                token,
+                min,
+                cooldown,
+                ignore_weekday,
+            } => {
+                let recipe = RadioRecipe::new(
+                    "Time of day",
+                    get_listen_filters(*min, *cooldown, None, command.keep_hated),
+                    RadioSorters::TimeOfDay {
+                        ignore_weekday: *ignore_weekday,
+                    },
+                );
+
+                custom_radio(
+                    conn,
+                    recipe,
+                    command.get_listen_seeder(username, token),
+                    command.get_token(username, token).as_deref(),
+                    collector,
+                    exporter,
+                    command.get_shuffle_temperature(),
+                )
+                .await?;
+            }
+
+            Self::Discovery { username, token } => {
+                discovery_radio(
+                    conn,
+                    command.get_listen_seeder(username, token),
+                    command.get_token(username, token).as_deref(),
+                    collector,
+                    exporter,
+                )
+                .await?;
+            }
+
+            Self::Custom { recipe, token } => {
+                let recipe = RadioRecipe::load(recipe)?;
+                let seeder = command.get_recipe_seeder(&recipe.seeder, token);
+                let token = command.get_token(&Some(seeder.username().clone()), token);
+
+                custom_radio(
+                    conn,
+                    recipe,
+                    seeder,
+                    token.as_deref(),
+                    collector,
+                    exporter,
+                    command.get_shuffle_temperature(),
                 )
                 .await?;
             }
+
+            Self::History(history) => history.run(command).await?,
         }
 
         Ok(())
     }
 }
 
-#[derive(ValueEnum, Clone, Debug)]
+#[derive(Parser, Debug, Clone, Serialize)]
+pub struct RadioHistoryCli {
+    #[command(subcommand)]
+    pub command: RadioHistoryCommands,
+}
+
+impl RadioHistoryCli {
+    pub async fn run(&self, radio: &RadioCommand) -> Result<(), crate::Error> {
+        match &self.command {
+            RadioHistoryCommands::List { limit } => list_radio_history(*limit).await,
+            RadioHistoryCommands::Export {
+                id,
+                username,
+                token,
+            } => {
+                export_radio_history(
+                    *id,
+                    radio.get_exporter(),
+                    username.clone().or_else(Config::get_default_user),
+                    radio.get_token(username, token).as_deref(),
+                )
+                .await
+            }
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RadioHistoryCommands {
+    /// List the latest generated radios
+    List {
+        /// How many radios to show
+        #[arg(short, long, default_value_t = 20)]
+        limit: u64,
+    },
+
+    /// Export a previous radio again. This uses the global `--output` and `--output-file` arguments
+    Export {
+        /// The id of the radio in the history
+        id: i64,
+
+        /// Name of the user to export the playlist for
+        username: Option<String>,
+
+        /// Your user token.
+        ///
+        /// You can find it at <https://listenbrainz.org/settings/>.
+        /// If it's set in the config file, you can ignore this argument
+        #[arg(short, long)]
+        #[serde(skip)]
+        token: Option<String>,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupMode {
+    /// Keep the recordings listened by all the users
+    Intersection,
+
+    /// Keep the recordings listened by any of the users
+    Union,
+
+    /// Keep the recordings listened by at least `--min-users` users
+    AtLeast,
+}
+
+impl GroupMode {
+    /// Get how many users need to have listened to a recording for it to be kept
+    pub fn get_min_users(&self, min_users: usize, user_count: usize) -> usize {
+        match self {
+            Self::Intersection => user_count,
+            Self::Union => 1,
+            Self::AtLeast => min_users,
+        }
+    }
+}
+
+impl Display for GroupMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Intersection => write!(f, "intersection"),
+            Self::Union => write!(f, "union"),
+            Self::AtLeast => write!(f, "at-least"),
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Debug, IsVariant, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum RadioExportTarget {
     Listenbrainz,
     Youtube,
-    //TODO: JSPF
+
+    /// Write the playlist as a JSPF file. Requires `--output-file`
+    Jspf,
+
+    /// Write the playlist as a M3U8 file of your local music files. Requires `--output-file` and `config music-directory`
+    M3u8,
+
+    /// Write the playlist as a XSPF file of your local music files. Requires `--output-file` and `config music-directory`
+    Xspf,
 }
 
 impl Display for RadioExportTarget {
@@ -326,6 +983,9 @@ impl Display for RadioExportTarget {
         match self {
             Self::Listenbrainz => write!(f, "listenbrainz"),
             Self::Youtube => write!(f, "youtube"),
+            Self::Jspf => write!(f, "jspf"),
+            Self::M3u8 => write!(f, "m3u8"),
+            Self::Xspf => write!(f, "xspf"),
         }
     }
 }
@@ -1,3 +1,10 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
 pub enum LintSeverity {
     MissingData,
     MissingRelation,
@@ -6,6 +13,15 @@ pub enum LintSeverity {
 }
 
 impl LintSeverity {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::MissingData => "missing_data",
+            Self::MissingRelation => "missing_relation",
+            Self::WrongData => "wrong_data",
+            Self::StyleIssue => "style_issue",
+        }
+    }
+
     pub fn get_color(&self) -> (u8, u8, u8) {
         match self {
             Self::MissingData => (32, 117, 191),
@@ -1,19 +1,39 @@
 use std::fmt::Display;
 
 use color_eyre::owo_colors::OwoColorize;
-use lint_severity::LintSeverity;
 use musicbrainz_db_lite::models::musicbrainz::main_entities::MainEntity;
+use serde::de::DeserializeOwned;
 
 pub mod lint_severity;
+pub mod report;
+pub mod start;
+
+/// Get the MusicBrainz type name and MBID of an entity
+pub fn get_entity_info(entity: &MainEntity) -> (&'static str, &str) {
+    match entity {
+        MainEntity::Artist(val) => ("artist", &val.mbid),
+        MainEntity::Label(val) => ("label", &val.mbid),
+        MainEntity::Recording(val) => ("recording", &val.mbid),
+        MainEntity::Release(val) => ("release", &val.mbid),
+        MainEntity::Work(val) => ("work", &val.mbid),
+    }
+}
 
 pub trait MbClippyLint: Sized {
+    /// The options of the lint, read from the `clippy.lints.<name>.options` field of the config file
+    type Options: DeserializeOwned + Default + 'static;
+
     async fn check(
         conn: &mut sqlx::SqliteConnection,
         entity: &MainEntity,
+        options: &Self::Options,
     ) -> Result<Option<Self>, crate::Error>;
 
     fn get_name() -> &'static str;
 
+    /// A short explanation of what the lint checks
+    fn get_description() -> &'static str;
+
     async fn get_body(
         &self,
         conn: &mut sqlx::SqliteConnection,
@@ -28,8 +48,6 @@ pub trait MbClippyLint: Sized {
         &self,
         conn: &mut sqlx::SqliteConnection,
     ) -> Result<Vec<MbClippyLintHint>, crate::Error>;
-
-    fn get_severity(&self) -> LintSeverity;
 }
 
 pub struct MbClippyLintLink {
@@ -53,6 +71,10 @@ impl MbClippyLintHint {
     pub fn new(text: String) -> Self {
         Self { text }
     }
+
+    pub fn get_text(&self) -> &str {
+        &self.text
+    }
 }
 
 impl Display for MbClippyLintHint {
@@ -0,0 +1,227 @@
+use std::fs;
+use std::path::Path;
+
+use clap::ValueEnum;
+use itertools::Itertools as _;
+use musicbrainz_db_lite::models::musicbrainz::main_entities::MainEntity;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::models::clippy::get_entity_info;
+use crate::models::clippy::lint_severity::LintSeverity;
+use crate::models::clippy::MbClippyLint;
+use crate::utils::cli::display::MainEntityExt as _;
+use crate::utils::regex::strip_terminal_formatting;
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ClippyReportFormat {
+    Json,
+
+    /// Static Analysis Results Interchange Format. This can be read by most code scanning tools
+    Sarif,
+
+    Markdown,
+}
+
+impl ClippyReportFormat {
+    pub fn get_extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Sarif => "sarif",
+            Self::Markdown => "md",
+        }
+    }
+}
+
+/// All the lints found during a non-interactive clippy run
+#[derive(Debug, Default, Serialize)]
+pub struct ClippyReport {
+    pub entries: Vec<ClippyReportEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClippyReportEntry {
+    pub lint: String,
+    pub severity: String,
+    pub entity_type: String,
+    pub entity_mbid: String,
+    pub entity_name: String,
+    pub body: String,
+    pub hints: Vec<String>,
+    pub links: Vec<ClippyReportLink>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClippyReportLink {
+    pub name: String,
+    pub url: String,
+}
+
+impl ClippyReport {
+    /// Add a lint hit to the report. The terminal formating of its texts is removed
+    pub async fn add_lint<L: MbClippyLint>(
+        &mut self,
+        conn: &mut sqlx::SqliteConnection,
+        entity: &MainEntity,
+        lint: &L,
+        severity: LintSeverity,
+    ) -> Result<(), crate::Error> {
+        let (entity_type, entity_mbid) = get_entity_info(entity);
+
+        self.entries.push(ClippyReportEntry {
+            lint: L::get_name().to_string(),
+            severity: severity.get_name().to_string(),
+            entity_type: entity_type.to_string(),
+            entity_mbid: entity_mbid.to_string(),
+            entity_name: strip_terminal_formatting(&entity.pretty_format(conn, false).await?),
+            body: strip_terminal_formatting(&lint.get_body(conn).await?.to_string()),
+            hints: lint
+                .get_hints(conn)
+                .await?
+                .iter()
+                .map(|hint| strip_terminal_formatting(hint.get_text()))
+                .collect(),
+            links: lint
+                .get_links(conn)
+                .await?
+                .into_iter()
+                .map(|link| ClippyReportLink {
+                    name: link.name,
+                    url: link.url,
+                })
+                .collect(),
+        });
+
+        Ok(())
+    }
+
+    pub fn save(&self, path: &Path, format: ClippyReportFormat) -> Result<(), crate::Error> {
+        let content = match format {
+            ClippyReportFormat::Json => {
+                serde_json::to_string_pretty(self).expect("The report is always serializable")
+            }
+            ClippyReportFormat::Sarif => serde_json::to_string_pretty(&self.to_sarif())
+                .expect("The report is always serializable"),
+            ClippyReportFormat::Markdown => self.to_markdown(),
+        };
+
+        fs::write(path, content).map_err(crate::Error::ClippyReportWriteError)
+    }
+
+    fn to_sarif(&self) -> serde_json::Value {
+        let rules = self
+            .entries
+            .iter()
+            .map(|entry| &entry.lint)
+            .unique()
+            .map(|lint| json!({ "id": lint, "name": lint }))
+            .collect_vec();
+
+        let results = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let mut message = entry.body.clone();
+                for hint in &entry.hints {
+                    message.push_str(&format!("\n{hint}"));
+                }
+
+                json!({
+                    "ruleId": entry.lint,
+                    "level": get_sarif_level(&entry.severity),
+                    "message": { "text": message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": {
+                                "uri": entry.get_url()
+                            }
+                        }
+                    }],
+                    "partialFingerprints": {
+                        "entityMbid": entry.entity_mbid
+                    },
+                    "properties": {
+                        "severity": entry.severity,
+                        "entityType": entry.entity_type,
+                        "entityName": entry.entity_name,
+                        "links": entry.links
+                    }
+                })
+            })
+            .collect_vec();
+
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "alistral musicbrainz clippy",
+                        "informationUri": "https://github.com/RustyNova016/Alistral",
+                        "rules": rules
+                    }
+                },
+                "results": results
+            }]
+        })
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# MusicBrainz clippy report\n\n{} issues found\n",
+            self.entries.len()
+        );
+
+        let by_lint = self
+            .entries
+            .iter()
+            .sorted_by(|a, b| a.lint.cmp(&b.lint))
+            .chunk_by(|entry| entry.lint.clone());
+
+        for (lint, entries) in &by_lint {
+            let entries = entries.collect_vec();
+            out.push_str(&format!("\n## {lint} ({})\n", entries.len()));
+
+            for entry in entries {
+                out.push_str(&format!(
+                    "\n### [{}]({})\n\nSeverity: {}\n\n{}\n",
+                    entry.entity_name,
+                    entry.get_url(),
+                    entry.severity,
+                    entry.body
+                ));
+
+                if !entry.hints.is_empty() {
+                    out.push('\n');
+                    for hint in &entry.hints {
+                        out.push_str(&format!("> {hint}\n"));
+                    }
+                }
+
+                out.push_str("\nLinks:\n");
+                for link in &entry.links {
+                    out.push_str(&format!("- [{}]({})\n", link.name, link.url));
+                }
+            }
+        }
+
+        out
+    }
+}
+
+impl ClippyReportEntry {
+    pub fn get_url(&self) -> String {
+        format!(
+            "https://musicbrainz.org/{}/{}",
+            self.entity_type, self.entity_mbid
+        )
+    }
+}
+
+fn get_sarif_level(severity: &str) -> &'static str {
+    match severity {
+        "wrong_data" => "error",
+        "style_issue" => "note",
+        _ => "warning",
+    }
+}
@@ -0,0 +1,52 @@
+use clap::ValueEnum;
+use interzic::models::clippy_crawl::ClippyCrawlSettings;
+use musicbrainz_db_lite::models::musicbrainz::main_entities::MainEntity;
+
+/// Where a clippy crawl starts from
+pub enum ClippyStart {
+    /// Start a new crawl from those entities
+    Entities {
+        nodes: Vec<MainEntity>,
+        settings: ClippyCrawlSettings,
+    },
+
+    /// Continue the crawl saved in the database, with its saved settings
+    Resume,
+}
+
+/// The kind of entity clippy starts crawling from
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClippyStartType {
+    Recording,
+    Artist,
+    Release,
+
+    /// Start from all the releases of the release group
+    ReleaseGroup,
+    Label,
+    Work,
+
+    /// Start from all the recordings or releases of a MusicBrainz collection
+    Collection,
+
+    /// Start from all the recordings listened by the user
+    Listens,
+}
+
+impl ClippyStartType {
+    /// Guess the type of the entity from its MusicBrainz URL
+    pub fn from_url(url: &str) -> Option<Self> {
+        let (_, path) = url.split_once("musicbrainz.org/")?;
+
+        match path.split('/').next()? {
+            "recording" => Some(Self::Recording),
+            "artist" => Some(Self::Artist),
+            "release" => Some(Self::Release),
+            "release-group" => Some(Self::ReleaseGroup),
+            "label" => Some(Self::Label),
+            "work" => Some(Self::Work),
+            "collection" => Some(Self::Collection),
+            _ => None,
+        }
+    }
+}
@@ -1,9 +1,10 @@
 use chrono::{DateTime, Utc};
+use derive_getters::Getters;
 use derive_new::new;
 use rust_decimal::{prelude::One, Decimal};
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, new, Default, Debug)]
+#[derive(Serialize, Deserialize, new, Default, Debug, Clone, Getters)]
 pub struct Bump {
     pub(self) recording: String,
     pub(self) username: String,
@@ -11,6 +12,16 @@ pub struct Bump {
     pub(self) expiration_date: DateTime<Utc>,
 }
 
+impl Bump {
+    pub fn is_expired(&self) -> bool {
+        self.expiration_date <= Utc::now()
+    }
+
+    pub fn is_for_user(&self, username: &str) -> bool {
+        self.username.eq_ignore_ascii_case(username)
+    }
+}
+
 #[derive(Serialize, Deserialize, new, Default, Debug)]
 pub struct BumpList(Vec<Bump>);
 
@@ -22,9 +33,6 @@ impl BumpList {
         value: Decimal,
         expiration_date: DateTime<Utc>,
     ) {
-        let now = Utc::now();
-        self.0.retain(|bump| bump.expiration_date > now);
-
         self.0.push(Bump {
             expiration_date,
             recording,
@@ -33,11 +41,14 @@ impl BumpList {
         });
     }
 
-    pub fn get_multiplier(&self, recording_mbid: &str) -> Decimal {
+    /// Get the multiplier of all the active bumps the user put on the recording
+    pub fn get_multiplier(&self, username: &str, recording_mbid: &str) -> Decimal {
         let values = self
             .0
             .iter()
-            .filter(|b| b.recording.as_str() == recording_mbid && b.expiration_date > Utc::now())
+            .filter(|b| {
+                b.recording.as_str() == recording_mbid && b.is_for_user(username) && !b.is_expired()
+            })
             .map(|b| b.value);
         let mut res = Decimal::one();
 
@@ -47,4 +58,36 @@ impl BumpList {
 
         res
     }
+
+    /// Get the bumps, sorted by expiration date. Only the bumps of the user are returned if set
+    pub fn get_bumps(&self, username: Option<&str>) -> Vec<&Bump> {
+        let mut bumps = self
+            .0
+            .iter()
+            .filter(|b| username.is_none_or(|username| b.is_for_user(username)))
+            .collect::<Vec<_>>();
+
+        bumps.sort_by_key(|b| b.expiration_date);
+        bumps
+    }
+
+    /// Remove all the bumps the user put on the recording. Returns the number of removed bumps
+    pub fn remove_bumps(&mut self, username: &str, recording_mbid: &str) -> usize {
+        self.remove_bumps_where(Some(username), |b| b.recording.as_str() == recording_mbid)
+    }
+
+    /// Remove the bumps matching the predicate. Only the bumps of the user are checked if set. Returns the number of removed bumps
+    pub fn remove_bumps_where(
+        &mut self,
+        username: Option<&str>,
+        predicate: impl Fn(&Bump) -> bool,
+    ) -> usize {
+        let before = self.0.len();
+        self.0.retain(|b| {
+            let is_target = username.is_none_or(|username| b.is_for_user(username));
+            !(is_target && predicate(b))
+        });
+
+        before - self.0.len()
+    }
 }
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::models::clippy::lint_severity::LintSeverity;
+
+/// The settings of `musicbrainz clippy`
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ClippyConfig {
+    /// The settings of each lint, by lint name
+    #[serde(default)]
+    pub lints: HashMap<String, ClippyLintConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ClippyLintConfig {
+    /// Whether the lint is checked. Lints are enabled by default
+    pub enabled: Option<bool>,
+
+    /// Replace the severity given by the lint
+    pub severity: Option<LintSeverity>,
+
+    /// The options specific to the lint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<serde_json::Value>,
+}
+
+impl ClippyConfig {
+    pub fn is_enabled(&self, lint: &str) -> bool {
+        self.lints
+            .get(lint)
+            .and_then(|conf| conf.enabled)
+            .unwrap_or(true)
+    }
+
+    pub fn get_severity_override(&self, lint: &str) -> Option<LintSeverity> {
+        self.lints.get(lint).and_then(|conf| conf.severity)
+    }
+
+    /// Read the options of the lint. Missing options use their default value
+    pub fn get_options<T: DeserializeOwned + Default>(
+        &self,
+        lint: &str,
+    ) -> Result<T, crate::Error> {
+        match self.lints.get(lint).and_then(|conf| conf.options.clone()) {
+            Some(options) => serde_json::from_value(options)
+                .map_err(|err| crate::Error::ClippyLintOptionsError(lint.to_string(), err)),
+            None => Ok(T::default()),
+        }
+    }
+
+    pub fn set_enabled(&mut self, lint: &str, enabled: bool) {
+        self.lints.entry(lint.to_string()).or_default().enabled = Some(enabled);
+    }
+
+    pub fn set_severity(&mut self, lint: &str, severity: Option<LintSeverity>) {
+        self.lints.entry(lint.to_string()).or_default().severity = severity;
+    }
+}
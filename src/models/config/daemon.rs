@@ -0,0 +1,184 @@
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Datelike as _;
+use chrono::Duration;
+use chrono::Local;
+use chrono::NaiveTime;
+use chrono::Weekday;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::datastructures::radio::collector::RadioCollector;
+use crate::datastructures::radio::collector::RadioCollectorOptions;
+use crate::datastructures::radio::exporter::RadioExporter;
+use crate::datastructures::radio::exporter::RadioExporterBuilder;
+use crate::models::cli::radio::RadioExportTarget;
+
+/// The radios generated by `alistral daemon`
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+pub struct DaemonConfig {
+    #[serde(default)]
+    pub schedule: Vec<ScheduledRadio>,
+}
+
+/// A radio recipe to generate at fixed times
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduledRadio {
+    /// The name of the entry in the logs. Uses the path of the recipe if unset
+    pub name: Option<String>,
+
+    /// The path to the radio recipe (See `radio custom`)
+    pub recipe: PathBuf,
+
+    /// The local times to generate the radio at, as "HH:MM"
+    pub at: Vec<String>,
+
+    /// Only generate the radio on those days of the week (Ex: ["mon", "fri"]). Every day is used if empty
+    #[serde(default)]
+    pub weekdays: Vec<String>,
+
+    /// Where to output the radio
+    #[serde(default = "default_output")]
+    pub output: RadioExportTarget,
+
+    /// The file to write the playlist to, for file based outputs
+    pub output_file: Option<PathBuf>,
+
+    /// The id or URL of a playlist to replace. This keeps the same playlist between each generation
+    pub replace: Option<String>,
+
+    /// The name of the playlist. If a playlist with this name already exists, it gets replaced
+    pub playlist_name: Option<String>,
+
+    /// The minimum count of tracks of the playlist
+    pub min_count: Option<u64>,
+
+    /// The minimum duration of the playlist. This accept natural language (Ex: "1 hour 36 mins")
+    pub min_duration: Option<String>,
+
+    /// The maximum duration of the playlist. This accept natural language (Ex: "2 hours")
+    pub max_duration: Option<String>,
+
+    /// Reorder the playlist so that similar tracks follow each other (See `radio --smooth-transitions`)
+    #[serde(default)]
+    pub smooth_transitions: bool,
+
+    /// A CSV file giving the BPM and key of recordings, used by `smooth_transitions`
+    pub transition_features: Option<PathBuf>,
+}
+
+impl ScheduledRadio {
+    pub fn get_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| self.recipe.display().to_string())
+    }
+
+    pub fn get_times(&self) -> Result<Vec<NaiveTime>, crate::Error> {
+        let mut times = self
+            .at
+            .iter()
+            .map(|time| {
+                NaiveTime::parse_from_str(time, "%H:%M").map_err(|_| {
+                    crate::Error::DaemonScheduleError(format!(
+                        "`{time}` isn't a valid time for `{}`. Use the format \"HH:MM\"",
+                        self.get_name()
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        times.sort();
+        Ok(times)
+    }
+
+    pub fn get_weekdays(&self) -> Result<Vec<Weekday>, crate::Error> {
+        self.weekdays
+            .iter()
+            .map(|day| {
+                day.parse().map_err(|_| {
+                    crate::Error::DaemonScheduleError(format!(
+                        "`{day}` isn't a valid day of the week for `{}`",
+                        self.get_name()
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Get the next time the radio should be generated, strictly after the given date
+    pub fn get_next_run(
+        &self,
+        after: DateTime<Local>,
+    ) -> Result<Option<DateTime<Local>>, crate::Error> {
+        let times = self.get_times()?;
+        let weekdays = self.get_weekdays()?;
+
+        for day_offset in 0..=7 {
+            let date = after.date_naive() + Duration::days(day_offset);
+
+            if !weekdays.is_empty() && !weekdays.contains(&date.weekday()) {
+                continue;
+            }
+
+            for time in &times {
+                let Some(run) = date.and_time(*time).and_local_timezone(Local).earliest() else {
+                    continue;
+                };
+
+                if run > after {
+                    return Ok(Some(run));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn get_collector(&self) -> Result<RadioCollector, crate::Error> {
+        RadioCollectorOptions {
+            min_count: self.min_count,
+            min_duration: self.min_duration.clone(),
+            max_duration: self.max_duration.clone(),
+            smooth_transitions: self.smooth_transitions,
+            transition_features: self.transition_features.clone(),
+            ..Default::default()
+        }
+        .build()
+    }
+
+    pub fn get_exporter(&self) -> RadioExporter {
+        let exporter = RadioExporterBuilder::default()
+            .target(self.output.clone())
+            .dry_run(false)
+            .explain_none()
+            .report_none()
+            .radio("custom".to_string())
+            .parameters(
+                serde_json::to_string(self).expect("Couldn't serialize the radio parameters"),
+            );
+
+        let exporter = match self.output_file.as_ref() {
+            Some(val) => exporter.output_file(val.clone()),
+            None => exporter.output_file_none(),
+        };
+
+        let exporter = match self.replace.as_ref() {
+            Some(val) => exporter.replace(val.clone()),
+            None => exporter.replace_none(),
+        };
+
+        let exporter = match self.playlist_name.as_ref() {
+            Some(val) => exporter.playlist_name(val.clone()),
+            None => exporter.playlist_name_none(),
+        };
+
+        exporter.build()
+    }
+}
+
+fn default_output() -> RadioExportTarget {
+    RadioExportTarget::Listenbrainz
+}
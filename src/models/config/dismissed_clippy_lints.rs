@@ -0,0 +1,38 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::config_trait::ConfigFile;
+
+/// The clippy lints that the user doesn't want to see again on specific entities
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct DismissedClippyLints(Vec<DismissedLint>);
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct DismissedLint {
+    lint: String,
+    mbid: String,
+
+    /// Why the lint is a false positive
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+impl DismissedClippyLints {
+    pub fn add(&mut self, lint: String, mbid: String, reason: Option<String>) {
+        if self.is_dismissed(&lint, &mbid) {
+            return;
+        }
+
+        self.0.push(DismissedLint { lint, mbid, reason });
+    }
+
+    pub fn is_dismissed(&self, lint: &str, mbid: &str) -> bool {
+        self.0.iter().any(|d| d.lint == lint && d.mbid == mbid)
+    }
+}
+
+impl ConfigFile for DismissedClippyLints {
+    fn file_name() -> &'static str {
+        "clippy_dismissed_lints.json"
+    }
+}
@@ -1,8 +1,10 @@
 pub mod whitelisted_wrong_mappings;
 use bumps::BumpList;
 use clap::CommandFactory;
+use clippy::ClippyConfig;
 use config_guard::ConfigGuard;
 use config_trait::ConfigFile;
+use daemon::DaemonConfig;
 use derive_getters::Getters;
 use listen_config::ListenConfig;
 use mapper::MapperConfig;
@@ -10,12 +12,16 @@ use rust_decimal::Decimal;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 use super::cli::Cli;
 
 pub mod bumps;
+pub mod clippy;
 pub mod config_guard;
 pub mod config_trait;
+pub mod daemon;
+pub mod dismissed_clippy_lints;
 pub mod global_config;
 pub mod listen_config;
 pub mod mapper;
@@ -38,11 +44,28 @@ pub struct Config {
 
     pub artist_listened_to: Option<Decimal>,
 
+    /// The multiplier applied to the radio scores of the recordings loved by the user
+    pub loved_multiplier: Option<Decimal>,
+
+    /// How many days the worldwide listen counts of ListenBrainz are kept before being fetched again
+    pub popularity_cache_days: Option<u32>,
+
     #[serde(default = "default_lb_url")]
     pub listenbrainz_url: String,
 
     #[serde(default = "default_mb_url")]
     pub musicbrainz_url: String,
+
+    /// The directory containing the user's music files
+    pub music_directory: Option<PathBuf>,
+
+    /// The radios to generate with `alistral daemon`
+    #[serde(default)]
+    pub daemon: DaemonConfig,
+
+    /// The lint settings of `musicbrainz clippy`
+    #[serde(default)]
+    pub clippy: ClippyConfig,
 }
 
 impl Config {
@@ -56,13 +79,22 @@ impl Config {
         self.tokens.insert(username.to_lowercase(), token);
     }
 
+    /// Get the token saved for the user, if any
+    pub fn get_saved_token(username: &str) -> Option<String> {
+        Self::load_or_panic()
+            .read_or_panic()
+            .tokens
+            .get(&username.to_lowercase())
+            .cloned()
+    }
+
     pub fn check_token(username: &str, arg: &Option<String>) -> String {
         if let Some(arg) = arg {
             return arg.clone();
         }
 
-        match Self::load_or_panic().read_or_panic().tokens.get(&username.to_lowercase()) {
-            Some(val) => val.clone(),
+        match Self::get_saved_token(username) {
+            Some(val) => val,
             None => {
                 Cli::command()
                     .error(
@@ -101,6 +133,14 @@ impl Config {
         self.artist_listened_to
             .unwrap_or_else(|| Decimal::new(2, 0))
     }
+
+    pub fn get_loved_multiplier(&self) -> Decimal {
+        self.loved_multiplier.unwrap_or_else(|| Decimal::new(12, 1))
+    }
+
+    pub fn get_popularity_cache_duration(&self) -> chrono::Duration {
+        chrono::Duration::days(self.popularity_cache_days.unwrap_or(7).into())
+    }
 }
 
 impl ConfigFile for Config {
@@ -113,6 +153,8 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             artist_listened_to: Some(Decimal::new(2, 0)),
+            loved_multiplier: Some(Decimal::new(12, 1)),
+            popularity_cache_days: Some(7),
             tokens: Default::default(),
             mapper: Default::default(),
             listens: Default::default(),
@@ -120,6 +162,9 @@ impl Default for Config {
             bumps: Default::default(),
             listenbrainz_url: default_lb_url(),
             musicbrainz_url: default_mb_url(),
+            music_directory: Default::default(),
+            daemon: Default::default(),
+            clippy: Default::default(),
         }
     }
 }
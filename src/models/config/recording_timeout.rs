@@ -29,6 +29,22 @@ impl RecordingTimeoutConfig {
             .cloned()
             .collect_vec()
     }
+
+    /// Get the recordings that are still timed out, with the end of their timeout. The earliest to end come first
+    pub fn get_timeouts(&self) -> Vec<(&String, &DateTime<Utc>)> {
+        let now = Utc::now();
+
+        self.0
+            .iter()
+            .filter(|(_, deadline)| &now < *deadline)
+            .sorted_by_key(|(_, deadline)| **deadline)
+            .collect_vec()
+    }
+
+    /// Remove the timeout of the recording. Returns false if it wasn't timed out
+    pub fn remove_timeout(&mut self, recording: &str) -> bool {
+        self.0.remove(recording).is_some()
+    }
 }
 
 impl ConfigFile for RecordingTimeoutConfig {
@@ -0,0 +1,33 @@
+use interzic::models::recording_feedback::RecordingFeedback;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct UserFeedbackResponse {
+    //TODO: Replace with listenbrainz_rs's
+    pub count: u64,
+    pub feedback: Vec<FeedbackItem>,
+    pub offset: u64,
+    pub total_count: u64,
+}
+
+/// A feedback given by a user. This is used by both the API and the `feedback.jsonl` file of the listen dumps
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct FeedbackItem {
+    pub created: i64,
+    pub recording_mbid: Option<String>,
+    pub recording_msid: Option<String>,
+    pub score: i64,
+}
+
+impl FeedbackItem {
+    /// Convert the feedback for the cache. Feedback given to unmapped recordings (MSID only) can't be used, so they return `None`
+    pub fn into_recording_feedback(self, username: &str) -> Option<RecordingFeedback> {
+        Some(RecordingFeedback {
+            username: username.to_lowercase(),
+            recording_mbid: self.recording_mbid?,
+            score: self.score,
+            created_at: self.created,
+        })
+    }
+}
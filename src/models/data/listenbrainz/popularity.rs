@@ -8,3 +8,11 @@ pub struct PopularityRecordingResponseItem {
     pub total_listen_count: Option<u64>,
     pub total_user_count: Option<u64>,
 }
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct PopularityReleaseGroupResponseItem {
+    //TODO: Replace with listenbrainz_rs's
+    pub release_group_mbid: String,
+    pub total_listen_count: Option<u64>,
+    pub total_user_count: Option<u64>,
+}
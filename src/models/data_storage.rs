@@ -13,6 +13,10 @@ pub struct DataStorage {
 }
 
 impl DataStorage {
+    pub fn get_playlist_count(&self) -> u64 {
+        self.playlist_count
+    }
+
     /// Increment the playlist count, returning the old count for usage
     pub fn incr_playlist_count(&mut self) -> u64 {
         let count = self.playlist_count;
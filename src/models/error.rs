@@ -26,6 +26,44 @@ pub enum Error {
     #[error("Couldn't write the configuration file.")]
     ConfigFileWriteError(serde_json::Error),
 
+    #[error("No music directory has been set. Set it with `config music-directory <PATH>`")]
+    MissingMusicDirectoryError,
+
+    // --- Radio Errors ---
+    #[error("Couldn't read the radio recipe file.")]
+    RadioRecipeLoadError(io::Error),
+
+    #[error("Couldn't parse the radio recipe: {0}")]
+    RadioRecipeParseError(toml::de::Error),
+
+    #[error("Invalid radio recipe: {0}")]
+    RadioRecipeValidationError(String),
+
+    #[error("Couldn't seed the radio: {0}")]
+    RadioSeederError(String),
+
+    #[error("Couldn't load the transition features: {0}")]
+    RadioTransitionFeaturesError(String),
+
+    #[error("Couldn't parse the duration `{0}`. Use natural language, like \"1 hour 36 mins\"")]
+    DurationParseError(String),
+
+    #[error("There is no radio with the id {0} in the history")]
+    MissingRadioHistoryError(i64),
+
+    #[error("Invalid radio schedule: {0}")]
+    DaemonScheduleError(String),
+
+    // --- Clippy Errors ---
+    #[error("Couldn't find where to start clippy: {0}")]
+    ClippyStartError(String),
+
+    #[error("Invalid options for the clippy lint `{0}`: {1}")]
+    ClippyLintOptionsError(String, serde_json::Error),
+
+    #[error("Couldn't write the clippy report")]
+    ClippyReportWriteError(io::Error),
+
     // --- Cache Errors ---
     #[error(transparent)]
     SQLxError(#[from] sqlx::Error),
@@ -52,6 +90,9 @@ pub enum Error {
     #[error("Listenbrainz responded with an error")]
     ListenbrainzError(#[from] listenbrainz::Error),
 
+    #[error("Musicbrainz responded with an error")]
+    MusicbrainzError(#[from] musicbrainz_rs::Error),
+
     #[error("No user data is available for this playlist export target: {0}")]
     MissingPlaylistUserDataError(String),
 }
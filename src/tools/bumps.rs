@@ -2,7 +2,9 @@ use std::str::FromStr;
 
 use alistral_core::datastructures::listen_collection::traits::ListenCollectionReadable as _;
 use chrono::Duration;
+use chrono::Local;
 use chrono::Utc;
+use itertools::Itertools as _;
 use musicbrainz_db_lite::models::musicbrainz::recording::Recording;
 use rust_decimal::Decimal;
 use tracing::info;
@@ -11,12 +13,17 @@ use crate::api::clients::ALISTRAL_CLIENT;
 use crate::database::listenbrainz::listens::ListenFetchQuery;
 use crate::database::listenbrainz::listens::ListenFetchQueryReturn;
 use crate::models::cli::BumpCLI;
+use crate::models::cli::BumpSubcommands;
 use crate::models::config::Config;
 use crate::utils::cli::display::RecordingExt as _;
 use crate::utils::cli::read_mbid_from_input;
 use crate::utils::extensions::chrono_ext::DurationExt as _;
 
 pub async fn bump_command(conn: &mut sqlx::SqliteConnection, bump: BumpCLI) {
+    if let Some(command) = bump.command {
+        return bump_subcommand(conn, command).await;
+    }
+
     let username = Config::check_username(&bump.username);
 
     let recording = match bump.recording {
@@ -80,3 +87,84 @@ pub async fn bump_down_command(conn: &mut sqlx::SqliteConnection, mut bump: Bump
     bump.multiplier = bump.multiplier.or_else(|| Some("0.9".to_string()));
     bump_command(conn, bump).await;
 }
+
+async fn bump_subcommand(conn: &mut sqlx::SqliteConnection, command: BumpSubcommands) {
+    match command {
+        BumpSubcommands::List { username, expired } => {
+            list_bumps(conn, username.as_deref(), expired).await;
+        }
+        BumpSubcommands::Remove {
+            recording,
+            username,
+        } => {
+            let username = Config::check_username(&username);
+            let mbid = read_mbid_from_input(&recording).expect("Couldn't parse MBID");
+
+            let conf = Config::load_or_panic();
+            let removed = conf.write_or_panic().bumps.remove_bumps(&username, &mbid);
+            info!("Removed {removed} bumps");
+        }
+        BumpSubcommands::Clear { expired, username } => {
+            let conf = Config::load_or_panic();
+            let removed = if expired {
+                conf.write_or_panic()
+                    .bumps
+                    .remove_bumps_where(username.as_deref(), |bump| bump.is_expired())
+            } else {
+                let username = Config::check_username(&username);
+                conf.write_or_panic()
+                    .bumps
+                    .remove_bumps_where(Some(&username), |_| true)
+            };
+            info!("Removed {removed} bumps");
+        }
+    }
+}
+
+/// Print the bumps, with the name of their recordings
+async fn list_bumps(conn: &mut sqlx::SqliteConnection, username: Option<&str>, expired: bool) {
+    // Don't keep the config locked while fetching the recordings
+    let bumps = Config::load_or_panic()
+        .read_or_panic()
+        .bumps
+        .get_bumps(username)
+        .into_iter()
+        .filter(|bump| expired || !bump.is_expired())
+        .cloned()
+        .collect_vec();
+
+    if bumps.is_empty() {
+        println!("No bumps found");
+        return;
+    }
+
+    for bump in bumps {
+        let recording =
+            Recording::get_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db, bump.recording())
+                .await
+                .expect("Couldn't get the recording");
+        let name = match recording {
+            Some(recording) => recording
+                .pretty_format_with_credits(conn, true)
+                .await
+                .expect("Error while getting recording credits"),
+            None => bump.recording().to_string(),
+        };
+
+        let expiration = bump
+            .expiration_date()
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M");
+        let status = if bump.is_expired() {
+            "expired"
+        } else {
+            "until"
+        };
+
+        println!(
+            "[{}] {name} - x{} ({status} {expiration})",
+            bump.username(),
+            bump.value()
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use core::panic::AssertUnwindSafe;
+
+use chrono::DateTime;
+use chrono::Local;
+use futures::FutureExt as _;
+use itertools::Itertools as _;
+use tracing::error;
+use tracing::info;
+
+use crate::datastructures::radio::recipe::RadioRecipe;
+use crate::models::config::daemon::ScheduledRadio;
+use crate::models::config::Config;
+use crate::tools::radio::custom::custom_radio;
+
+/// Generate the scheduled radios of the config at their set times. This runs until the process is stopped
+pub async fn run_daemon(conn: &mut sqlx::SqliteConnection, once: bool) -> Result<(), crate::Error> {
+    let schedule = Config::load_or_panic()
+        .read_or_panic()
+        .daemon
+        .schedule
+        .clone();
+
+    if schedule.is_empty() {
+        println!("No radios are scheduled. Add some to the `daemon.schedule` section of the configuration file");
+        return Ok(());
+    }
+
+    // Check the schedule beforehand, so mistakes are found at startup instead of at generation time
+    for entry in &schedule {
+        entry.get_times()?;
+        entry.get_weekdays()?;
+        entry.get_collector()?;
+        let seeder = RadioRecipe::load(&entry.recipe)?.seeder.get_listen_seeder();
+
+        if entry.output.is_listenbrainz() {
+            Config::check_token(seeder.username(), &None);
+        }
+    }
+
+    if once {
+        for entry in &schedule {
+            run_scheduled_radio(conn, entry).await;
+        }
+
+        return Ok(());
+    }
+
+    let mut after = Local::now();
+    loop {
+        let Some(next_run) = get_next_run(&schedule, after)? else {
+            println!(
+                "None of the scheduled radios can run. Check their `at` and `weekdays` fields"
+            );
+            return Ok(());
+        };
+
+        let entries = schedule
+            .iter()
+            .filter(|entry| entry.get_next_run(after).ok().flatten() == Some(next_run))
+            .collect_vec();
+
+        info!(
+            "Next generation at {}: {}",
+            next_run.format("%Y-%m-%d %H:%M"),
+            entries.iter().map(|entry| entry.get_name()).join(", ")
+        );
+
+        let wait = (next_run - Local::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+
+        for entry in entries {
+            run_scheduled_radio(conn, entry).await;
+        }
+
+        after = next_run;
+    }
+}
+
+fn get_next_run(
+    schedule: &[ScheduledRadio],
+    after: DateTime<Local>,
+) -> Result<Option<DateTime<Local>>, crate::Error> {
+    let mut next_runs = Vec::with_capacity(schedule.len());
+    for entry in schedule {
+        next_runs.extend(entry.get_next_run(after)?);
+    }
+
+    Ok(next_runs.into_iter().min())
+}
+
+/// Generate a scheduled radio, logging the result. A failing radio doesn't stop the daemon
+async fn run_scheduled_radio(conn: &mut sqlx::SqliteConnection, entry: &ScheduledRadio) {
+    let name = entry.get_name();
+    info!("Generating radio `{name}`");
+
+    let result = AssertUnwindSafe(generate_scheduled_radio(conn, entry))
+        .catch_unwind()
+        .await;
+
+    match result {
+        Ok(Ok(())) => info!("Generated radio `{name}`"),
+        Ok(Err(err)) => error!("Couldn't generate radio `{name}`: {err}"),
+        Err(_) => error!("Couldn't generate radio `{name}`: the generation crashed"),
+    }
+}
+
+async fn generate_scheduled_radio(
+    conn: &mut sqlx::SqliteConnection,
+    entry: &ScheduledRadio,
+) -> Result<(), crate::Error> {
+    let recipe = RadioRecipe::load(&entry.recipe)?;
+    let seeder = recipe.seeder.get_listen_seeder();
+
+    let token = entry
+        .output
+        .is_listenbrainz()
+        .then(|| Config::check_token(seeder.username(), &None));
+
+    custom_radio(
+        conn,
+        recipe,
+        seeder,
+        token.as_deref(),
+        entry.get_collector()?,
+        entry.get_exporter(),
+        None,
+    )
+    .await
+}
@@ -4,6 +4,7 @@ use std::io::BufRead;
 use std::io::BufReader;
 use std::path::Path;
 
+use interzic::models::recording_feedback::RecordingFeedback;
 use musicbrainz_db_lite::models::listenbrainz::listen::Listen;
 use musicbrainz_db_lite::models::listenbrainz::messybrainz_submission::MessybrainzSubmission;
 use musicbrainz_db_lite::models::listenbrainz::msid_mapping::MsidMapping;
@@ -14,6 +15,9 @@ use serde::Serialize;
 use sqlx::Acquire;
 use tracing::info;
 
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::models::data::listenbrainz::feedback::FeedbackItem;
+
 pub async fn import_listen_dump(
     conn: &mut sqlx::SqliteConnection,
     dump_path: &Path,
@@ -23,6 +27,7 @@ pub async fn import_listen_dump(
     let mut archive = zip::ZipArchive::new(zip_file).expect("Couldn't read zip file.");
 
     let mut import_trans = conn.begin().await.expect("Couldn't start transaction");
+    let mut feedback: Vec<FeedbackItem> = Vec::new();
 
     // We read the zip file
     for i in 0..archive.len() {
@@ -38,9 +43,20 @@ pub async fn import_listen_dump(
             continue;
         }
 
+        if outpath.to_string_lossy() == "feedback.jsonl" {
+            feedback = BufReader::new(file)
+                .lines()
+                .map(|line| {
+                    let line = line.expect("Couldn't read line");
+                    serde_json::from_str(&line)
+                        .unwrap_or_else(|err| panic!("Couldn't convert feedback. Error: {err}"))
+                })
+                .collect();
+            continue;
+        }
+
         // The file is actually a listen?
-        if outpath.to_string_lossy() == "feedback.jsonl"
-            || outpath.to_string_lossy() == "pinned_recording.jsonl"
+        if outpath.to_string_lossy() == "pinned_recording.jsonl"
             || outpath.to_string_lossy() == "user.json"
         {
             continue;
@@ -81,6 +97,21 @@ pub async fn import_listen_dump(
         .commit()
         .await
         .expect("Couldn't save transaction");
+
+    // The feedback is kept in the interzic database, so it is saved separately
+    let feedback = feedback
+        .into_iter()
+        .filter_map(|item| item.into_recording_feedback(username))
+        .collect::<Vec<_>>();
+    RecordingFeedback::replace_user_feedback(
+        &ALISTRAL_CLIENT.interzic.database_client,
+        username,
+        &feedback,
+    )
+    .await
+    .expect("Couldn't save feedback");
+
+    info!("Loaded {} feedback", feedback.len());
 }
 
 //TODO: #449 Move ImportListen to models
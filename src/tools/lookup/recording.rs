@@ -40,7 +40,7 @@ pub async fn lookup_recording(
     println!(
         "{}",
         target_recording
-            .get_lookup_report(conn, &all_listens)
+            .get_lookup_report(conn, username, &all_listens)
             .await
             .expect("Couldn't generate lookup report")
     );
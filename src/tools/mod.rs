@@ -1,10 +1,12 @@
 pub mod bumps;
 pub mod cache;
 pub mod compatibility;
+pub mod daemon;
 pub mod daily;
 pub mod listens;
 pub mod lookup;
 pub mod musicbrainz;
 pub mod radio;
 pub mod stats;
+pub mod timeouts;
 pub mod unstable;
@@ -1,107 +1,350 @@
+use std::collections::HashMap;
 use std::collections::VecDeque;
+use std::path::PathBuf;
 
 use alistral_core::cli::colors::AlistralColors as _;
+use alistral_core::datastructures::entity_with_listens::recording::collection::RecordingWithListensCollection;
 use color_eyre::owo_colors::OwoColorize as _;
 use futures::TryStreamExt;
+use inquire::InquireError;
+use inquire::Select;
+use inquire::Text;
+use interzic::models::clippy_crawl::ClippyCrawlChanges;
+use interzic::models::clippy_crawl::ClippyCrawlNode;
+use interzic::models::clippy_crawl::ClippyCrawlState;
+use itertools::Itertools as _;
+use musicbrainz_db_lite::models::musicbrainz::artist::Artist;
+use musicbrainz_db_lite::models::musicbrainz::label::Label;
 use musicbrainz_db_lite::models::musicbrainz::main_entities::MainEntity;
 use musicbrainz_db_lite::models::musicbrainz::recording::Recording;
+use musicbrainz_db_lite::models::musicbrainz::release::Release;
+use musicbrainz_db_lite::models::musicbrainz::work::Work;
+use musicbrainz_rs::entity::recording::Recording as MBRecording;
+use musicbrainz_rs::entity::release::Release as MBRelease;
+use musicbrainz_rs::Browse as _;
 use tracing::info;
 
 use crate::api::clients::ALISTRAL_CLIENT;
-use crate::datastructures::clippy::missing_release_barcode::MissingBarcodeLint;
-use crate::datastructures::clippy::missing_remix_rel::MissingRemixRelLint;
-use crate::datastructures::clippy::missing_remixer_rel::MissingRemixerRelLint;
-use crate::datastructures::clippy::missing_work::MissingWorkLint;
-use crate::datastructures::clippy::soundtrack_without_disambiguation::SoundtrackWithoutDisambiguationLint;
-use crate::datastructures::clippy::suspicious_remix::SuspiciousRemixLint;
+use crate::api::musicbrainz::browse_all;
+use crate::api::musicbrainz::cache::clear_cached;
+use crate::api::musicbrainz::BROWSE_LIMIT;
+use crate::database::listenbrainz::listens::ListenFetchQuery;
+use crate::database::listenbrainz::listens::ListenFetchQueryReturn;
+use crate::datastructures::clippy::registry::ClippyLintChecker;
+use crate::datastructures::clippy::registry::ClippyLintKind;
+use crate::models::clippy::get_entity_info;
+use crate::models::clippy::lint_severity::LintSeverity;
+use crate::models::clippy::report::ClippyReport;
+use crate::models::clippy::report::ClippyReportFormat;
+use crate::models::clippy::start::ClippyStart;
+use crate::models::clippy::start::ClippyStartType;
 use crate::models::clippy::MbClippyLint;
-use crate::utils::cli::await_next;
+use crate::models::config::config_guard::ConfigGuard;
+use crate::models::config::config_trait::ConfigFile as _;
+use crate::models::config::dismissed_clippy_lints::DismissedClippyLints;
+use crate::models::config::Config;
 use crate::utils::cli::display::MainEntityExt;
-use crate::utils::whitelist_blacklist::WhitelistBlacklist;
+use crate::utils::cli::read_mbid_from_input;
 
+/// The number of checked entities between each save of the crawl progress and the report
+const SAVE_INTERVAL: i64 = 10;
+
+/// Crawl the database from the start entities, and show the lints of each entity.
+///
+/// The crawl stops after `max_count` entities, and doesn't go further than `max_depth` relations away from the start entities.
+/// If a report format is given, the lints are written to the report file instead of being shown one by one.
+/// The report file is rewritten along with the crawl progress, so the lints found so far aren't lost if the crawl gets interrupted.
+///
+/// The progress is saved in the database, so an unfinished crawl can be resumed later
 pub async fn mb_clippy(
     conn: &mut sqlx::SqliteConnection,
-    start_mbid: &str,
-    new_first: bool,
-    filter: &WhitelistBlacklist<String>,
+    start: ClippyStart,
+    lints: &[Box<dyn ClippyLintChecker>],
+    report_output: Option<(ClippyReportFormat, PathBuf)>,
 ) {
-    let start_node = Recording::fetch_and_save(conn, &ALISTRAL_CLIENT.musicbrainz_db, start_mbid)
-        .await
-        .unwrap()
-        .expect("Couldn't find MBID");
+    let mut report = report_output.as_ref().map(|_| ClippyReport::default());
+    let dismissed = DismissedClippyLints::load().expect("Couldn't load the dismissed lints");
 
-    let mut queue = VecDeque::new();
-    queue.push_back(MainEntity::Recording(start_node));
-    let mut seen = Vec::new();
+    let (settings, mut queue, mut seen) = match start {
+        ClippyStart::Entities { nodes, settings } => {
+            let queue = ClippyQueue::new(nodes.iter().map(get_entity_info).enumerate().map(
+                |(position, (entity_type, mbid))| ClippyCrawlNode {
+                    position: position as i64,
+                    entity_type: entity_type.to_string(),
+                    mbid: mbid.to_string(),
+                    depth: 0,
+                },
+            ));
 
-    while let Some(mut entity) = get_new_element(&mut queue, new_first) {
-        if seen
-            .iter()
-            .any(|done: &MainEntity| done.is_equal_by_mbid(&entity))
-        {
+            ClippyCrawlState {
+                settings: settings.clone(),
+                queue: queue.nodes.iter().cloned().collect(),
+                seen: Vec::new(),
+            }
+            .save(&ALISTRAL_CLIENT.interzic.database_client)
+            .await
+            .expect("Couldn't save the crawl progress");
+
+            (settings, queue, HashMap::new())
+        }
+        ClippyStart::Resume => {
+            let Some(state) = ClippyCrawlState::load(&ALISTRAL_CLIENT.interzic.database_client)
+                .await
+                .expect("Couldn't load the saved crawl")
+            else {
+                println!("There is no saved crawl to resume");
+                return;
+            };
+
+            (
+                state.settings,
+                ClippyQueue::new(state.queue),
+                state.seen.into_iter().collect::<HashMap<_, _>>(),
+            )
+        }
+    };
+
+    let mut checked = 0;
+    while settings.max_count.is_none_or(|max| checked < max) {
+        let Some(node) = queue.pop(settings.new_first) else {
+            break;
+        };
+
+        // With `new_first`, an entity can be reached deeper first. It then needs to be expanded again from the shallower depth,
+        // but its lints don't need to be checked twice
+        let seen_depth = seen.get(&node.mbid).copied();
+        if seen_depth.is_some_and(|seen_depth| seen_depth <= node.depth) {
             continue;
         }
 
-        entity
-            .refetch_and_load(conn, &ALISTRAL_CLIENT.musicbrainz_db)
+        let Some(mut entity) = fetch_entity(conn, &node.entity_type, &node.mbid)
             .await
-            .expect("Couldn't fetch entity");
-
-        check_lint::<MissingWorkLint>(conn, &mut entity, filter).await;
-        check_lint::<MissingBarcodeLint>(conn, &mut entity, filter).await;
-        check_lint::<SuspiciousRemixLint>(conn, &mut entity, filter).await;
-        check_lint::<MissingRemixRelLint>(conn, &mut entity, filter).await;
-        check_lint::<MissingRemixerRelLint>(conn, &mut entity, filter).await;
-        check_lint::<SoundtrackWithoutDisambiguationLint>(conn, &mut entity, filter).await;
+            .expect("Couldn't fetch entity")
+        else {
+            continue;
+        };
 
-        println!(
-            "Checked {}",
+        if seen_depth.is_none() {
             entity
+                .refetch_and_load(conn, &ALISTRAL_CLIENT.musicbrainz_db)
+                .await
+                .expect("Couldn't fetch entity");
+
+            if !check_all_lints(conn, &mut entity, lints, &dismissed, report.as_mut()).await {
+                // Put the entity back, so it gets checked again when resuming
+                queue.push(node, settings.new_first);
+                break;
+            }
+
+            let name = entity
                 .pretty_format(conn, false)
                 .await
-                .expect("Error while formating the name of the entity")
-        );
-        println!();
+                .expect("Error while formating the name of the entity");
+            if report.is_some() {
+                info!("Checked {name}");
+            } else {
+                println!("Checked {name}");
+                println!();
+            }
+        }
 
-        get_new_nodes(conn, &entity, &mut queue)
+        if settings.max_depth.is_none_or(|max| node.depth < max) {
+            let new_nodes = get_new_nodes(conn, &entity)
+                .await
+                .expect("Couldn't get new items to process");
+
+            for new_node in new_nodes {
+                let (entity_type, mbid) = get_entity_info(&new_node);
+                queue.push_front(entity_type.to_string(), mbid.to_string(), node.depth + 1);
+            }
+        }
+
+        seen.insert(node.mbid.clone(), node.depth);
+        queue.changes.seen.push((node.mbid, node.depth));
+        if seen_depth.is_some() {
+            continue;
+        }
+
+        checked += 1;
+
+        if checked % SAVE_INTERVAL == 0 {
+            queue
+                .changes
+                .save(&ALISTRAL_CLIENT.interzic.database_client)
+                .await
+                .expect("Couldn't save the crawl progress");
+
+            if let (Some(report), Some((format, path))) = (&report, &report_output) {
+                report
+                    .save(path, *format)
+                    .expect("Couldn't save the clippy report");
+            }
+        }
+    }
+
+    if queue.nodes.is_empty() {
+        ClippyCrawlState::clear(&ALISTRAL_CLIENT.interzic.database_client)
+            .await
+            .expect("Couldn't clear the crawl progress");
+        println!("No more data to process");
+    } else {
+        queue
+            .changes
+            .save(&ALISTRAL_CLIENT.interzic.database_client)
             .await
-            .expect("Couldn't get new items to process");
+            .expect("Couldn't save the crawl progress");
+        println!("Stopped after checking {checked} entities. Use `--resume` to continue");
+    }
 
-        seen.push(entity);
+    if let (Some(report), Some((format, path))) = (report, report_output) {
+        report
+            .save(&path, format)
+            .expect("Couldn't save the clippy report");
+        println!("Saved {} lints to {}", report.entries.len(), path.display());
     }
+}
 
-    println!("No more data to process");
+/// The entities left to check, along with the changes to save in the crawl progress
+struct ClippyQueue {
+    nodes: VecDeque<ClippyCrawlNode>,
+    changes: ClippyCrawlChanges,
+
+    /// The smallest and biggest positions given out. Positions aren't reused, so a removed node can't be mistaken for a new one
+    first_position: i64,
+    last_position: i64,
 }
 
-fn get_new_element(queue: &mut VecDeque<MainEntity>, new_first: bool) -> Option<MainEntity> {
-    if new_first {
-        queue.pop_front()
-    } else {
-        queue.pop_back()
+impl ClippyQueue {
+    fn new(nodes: impl IntoIterator<Item = ClippyCrawlNode>) -> Self {
+        let nodes: VecDeque<ClippyCrawlNode> = nodes.into_iter().collect();
+
+        Self {
+            first_position: nodes.front().map(|node| node.position).unwrap_or(0),
+            last_position: nodes.back().map(|node| node.position).unwrap_or(0),
+            nodes,
+            changes: ClippyCrawlChanges::default(),
+        }
+    }
+
+    fn pop(&mut self, new_first: bool) -> Option<ClippyCrawlNode> {
+        let node = if new_first {
+            self.nodes.pop_front()
+        } else {
+            self.nodes.pop_back()
+        }?;
+
+        self.changes.removed.push(node.position);
+        Some(node)
+    }
+
+    fn push_front(&mut self, entity_type: String, mbid: String, depth: i64) {
+        self.first_position -= 1;
+        let node = ClippyCrawlNode {
+            position: self.first_position,
+            entity_type,
+            mbid,
+            depth,
+        };
+
+        self.changes.added.push(node.clone());
+        self.nodes.push_front(node);
+    }
+
+    /// Put a popped node back where it got popped from
+    fn push(&mut self, node: ClippyCrawlNode, new_first: bool) {
+        // The node gets a new position, as its old one is already marked as removed
+        let position = if new_first {
+            self.first_position -= 1;
+            self.first_position
+        } else {
+            self.last_position += 1;
+            self.last_position
+        };
+        let node = ClippyCrawlNode { position, ..node };
+
+        self.changes.added.push(node.clone());
+        if new_first {
+            self.nodes.push_front(node);
+        } else {
+            self.nodes.push_back(node);
+        }
+    }
+}
+
+/// Print all the lints, with their settings from the config file
+pub fn print_lint_list() {
+    let config = Config::load_or_panic().read_or_panic().clippy.clone();
+
+    for lint in ClippyLintKind::ALL {
+        let name = lint.get_name();
+        let severity = config
+            .get_severity_override(name)
+            .unwrap_or(lint.get_default_severity());
+
+        let mut status = vec![format!("severity: {}", severity.get_name())];
+        if !config.is_enabled(name) {
+            status.push("disabled".to_string());
+        }
+
+        println!("{} ({})", name.bold(), status.join(", "));
+        println!("    {}", lint.get_description());
     }
 }
 
-async fn check_lint<L: MbClippyLint>(
+/// Check the lints on the entity. Returns `false` if the user wants to stop
+async fn check_all_lints(
     conn: &mut sqlx::SqliteConnection,
     entity: &mut MainEntity,
-    filter: &WhitelistBlacklist<String>,
-) {
-    // Check if the lint is allowed
-    if !filter.is_allowed(&L::get_name().to_string()) {
-        return;
+    lints: &[Box<dyn ClippyLintChecker>],
+    dismissed: &ConfigGuard<DismissedClippyLints>,
+    mut report: Option<&mut ClippyReport>,
+) -> bool {
+    for lint in lints {
+        if !lint
+            .check(conn, entity, dismissed, report.as_deref_mut())
+            .await
+        {
+            return false;
+        }
     }
 
-    let Some(lint) = L::check(conn, entity)
+    true
+}
+
+/// Check a lint on the entity. Returns `false` if the user wants to stop
+pub async fn check_lint<L: MbClippyLint>(
+    conn: &mut sqlx::SqliteConnection,
+    entity: &mut MainEntity,
+    options: &L::Options,
+    severity: LintSeverity,
+    dismissed: &ConfigGuard<DismissedClippyLints>,
+    report: Option<&mut ClippyReport>,
+) -> bool {
+    let Some(lint) = L::check(conn, entity, options)
         .await
         .expect("Error while processing lint")
     else {
-        return;
+        return true;
     };
 
+    let mbid = get_entity_info(entity).1.to_string();
+    if dismissed.read_or_panic().is_dismissed(L::get_name(), &mbid) {
+        return true;
+    }
+
+    // Reports don't wait for the user to fix the lint
+    if let Some(report) = report {
+        report
+            .add_lint(conn, entity, &lint, severity)
+            .await
+            .expect("Error while adding the lint to the report");
+        return true;
+    }
+
     println!(
         "{}",
         format!("\n {} ", L::get_name())
-            .on_truecolor_tup(lint.get_severity().get_color())
+            .on_truecolor_tup(severity.get_color())
             .black()
             .bold()
     );
@@ -137,71 +380,249 @@ async fn check_lint<L: MbClippyLint>(
     }
 
     println!();
-    await_next();
-    entity
-        .refetch_and_load(conn, &ALISTRAL_CLIENT.musicbrainz_db)
-        .await
-        .expect("Couldn't fetch entity");
+    match choice() {
+        Choice::Next => {
+            entity
+                .refetch_and_load(conn, &ALISTRAL_CLIENT.musicbrainz_db)
+                .await
+                .expect("Couldn't fetch entity");
+
+            // The user may have fixed the lint, so the cached API data is outdated
+            let (entity_type, mbid) = get_entity_info(entity);
+            clear_cached(entity_type, mbid)
+                .await
+                .expect("Couldn't clear the cached entity");
+            true
+        }
+        Choice::Dismiss => {
+            let reason = Text::new("Why is it a false positive? (Optional)")
+                .prompt()
+                .ok()
+                .filter(|reason| !reason.trim().is_empty());
+
+            dismissed
+                .write_or_panic()
+                .add(L::get_name().to_string(), mbid, reason);
+            true
+        }
+        Choice::Exit => false,
+    }
+}
+
+#[derive(strum_macros::Display)]
+enum Choice {
+    Next,
+    #[strum(to_string = "Dismiss the lint for this entity")]
+    Dismiss,
+    Exit,
+}
+
+fn choice() -> Choice {
+    loop {
+        let options = vec![Choice::Next, Choice::Dismiss, Choice::Exit];
+
+        let ans = Select::new("", options).prompt();
+
+        match ans {
+            Ok(choice) => return choice,
+            Err(InquireError::OperationCanceled) | Err(InquireError::OperationInterrupted) => {
+                return Choice::Exit
+            }
+            _ => println!("There was an error, please try again"),
+        }
+    }
 }
 
 async fn get_new_nodes(
     conn: &mut sqlx::SqliteConnection,
     entity: &MainEntity,
-    queue: &mut VecDeque<MainEntity>,
-) -> Result<(), crate::Error> {
+) -> Result<Vec<MainEntity>, crate::Error> {
     info!("Getting new data...");
+    let mut nodes = Vec::new();
 
     match entity {
         MainEntity::Recording(val) => {
             let artists = val
                 .get_artists_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db)
                 .await?;
-            for artist in artists {
-                queue.push_front(MainEntity::Artist(artist));
-            }
+            nodes.extend(artists.into_iter().map(MainEntity::Artist));
 
             let releases = val
                 .get_releases_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db)
                 .await?;
-            for release in releases {
-                queue.push_front(MainEntity::Release(release));
-            }
+            nodes.extend(releases.into_iter().map(MainEntity::Release));
 
             let works = val
                 .get_works_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db)
                 .await?;
-            for work in works {
-                queue.push_front(MainEntity::Work(work));
-            }
+            nodes.extend(works.into_iter().map(MainEntity::Work));
         }
         MainEntity::Release(val) => {
             let recordings = val
                 .get_recordings_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db)
                 .await?;
-            for recording in recordings {
-                queue.push_front(MainEntity::Recording(recording));
-            }
+            nodes.extend(recordings.into_iter().map(MainEntity::Recording));
         }
         MainEntity::Artist(val) => {
             let recordings: Vec<Recording> = val
                 .browse_or_fetch_artist_recordings(conn)
                 .try_collect()
                 .await?;
-            for recording in recordings {
-                queue.push_front(MainEntity::Recording(recording));
-            }
+            nodes.extend(recordings.into_iter().map(MainEntity::Recording));
         }
         _ => {}
     }
 
-    Ok(())
+    Ok(nodes)
+}
+
+/// Get the entities to start crawling from
+pub async fn get_start_nodes(
+    conn: &mut sqlx::SqliteConnection,
+    start_type: ClippyStartType,
+    value: &str,
+) -> Result<Vec<MainEntity>, crate::Error> {
+    if start_type == ClippyStartType::Listens {
+        let listens = ListenFetchQuery::builder()
+            .returns(ListenFetchQueryReturn::Mapped)
+            .user(value.to_string())
+            .build()
+            .fetch(conn)
+            .await?;
+
+        let recordings =
+            RecordingWithListensCollection::from_listencollection(conn, &ALISTRAL_CLIENT, listens)
+                .await?;
+
+        return Ok(recordings
+            .iter_entities()
+            .cloned()
+            .map(MainEntity::Recording)
+            .collect_vec());
+    }
+
+    let mbid = read_mbid_from_input(value).ok_or_else(|| {
+        crate::Error::ClippyStartError(format!("`{value}` isn't a valid MBID or URL"))
+    })?;
+    let node = match start_type {
+        ClippyStartType::Recording => fetch_entity(conn, "recording", &mbid).await?,
+        ClippyStartType::Artist => fetch_entity(conn, "artist", &mbid).await?,
+        ClippyStartType::Release => fetch_entity(conn, "release", &mbid).await?,
+        ClippyStartType::Label => fetch_entity(conn, "label", &mbid).await?,
+        ClippyStartType::Work => fetch_entity(conn, "work", &mbid).await?,
+        ClippyStartType::ReleaseGroup => {
+            let release_group = mbid.as_str();
+            let releases = browse_all(|offset| async move {
+                MBRelease::browse()
+                    .by_release_group(release_group)
+                    .limit(BROWSE_LIMIT)
+                    .offset(offset)
+                    .execute_with_client(&ALISTRAL_CLIENT.musicbrainz_rs)
+                    .await
+            })
+            .await?;
+
+            return get_releases(conn, releases.into_iter().map(|r| r.id)).await;
+        }
+        ClippyStartType::Collection => return get_collection_nodes(conn, &mbid).await,
+        ClippyStartType::Listens => unreachable!("Listens are handled above"),
+    };
+
+    node.map(|node| vec![node])
+        .ok_or_else(|| crate::Error::ClippyStartError(format!("Couldn't find the entity `{mbid}`")))
+}
+
+/// Get an entity from its MusicBrainz type name and MBID
+async fn fetch_entity(
+    conn: &mut sqlx::SqliteConnection,
+    entity_type: &str,
+    mbid: &str,
+) -> Result<Option<MainEntity>, crate::Error> {
+    let client = &ALISTRAL_CLIENT.musicbrainz_db;
+
+    Ok(match entity_type {
+        "recording" => Recording::get_or_fetch(conn, client, mbid)
+            .await?
+            .map(MainEntity::Recording),
+        "artist" => Artist::get_or_fetch(conn, client, mbid)
+            .await?
+            .map(MainEntity::Artist),
+        "release" => Release::get_or_fetch(conn, client, mbid)
+            .await?
+            .map(MainEntity::Release),
+        "label" => Label::get_or_fetch(conn, client, mbid)
+            .await?
+            .map(MainEntity::Label),
+        "work" => Work::get_or_fetch(conn, client, mbid)
+            .await?
+            .map(MainEntity::Work),
+        _ => None,
+    })
+}
+
+/// Get the recordings or the releases of a collection. Collections of other entities aren't supported
+async fn get_collection_nodes(
+    conn: &mut sqlx::SqliteConnection,
+    collection: &str,
+) -> Result<Vec<MainEntity>, crate::Error> {
+    // Collections can either hold recordings or releases. Fetch both, as one of them will be empty
+    let recording_mbids = browse_all(|offset| async move {
+        MBRecording::browse()
+            .by_collection(collection)
+            .limit(BROWSE_LIMIT)
+            .offset(offset)
+            .execute_with_client(&ALISTRAL_CLIENT.musicbrainz_rs)
+            .await
+    })
+    .await?
+    .into_iter()
+    .map(|r| r.id);
+
+    let release_mbids = browse_all(|offset| async move {
+        MBRelease::browse()
+            .by_collection(collection)
+            .limit(BROWSE_LIMIT)
+            .offset(offset)
+            .execute_with_client(&ALISTRAL_CLIENT.musicbrainz_rs)
+            .await
+    })
+    .await?
+    .into_iter()
+    .map(|r| r.id);
+
+    let mut nodes = get_releases(conn, release_mbids).await?;
+    for mbid in recording_mbids {
+        if let Some(recording) =
+            Recording::get_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db, &mbid).await?
+        {
+            nodes.push(MainEntity::Recording(recording));
+        }
+    }
+
+    Ok(nodes)
+}
+
+async fn get_releases(
+    conn: &mut sqlx::SqliteConnection,
+    mbids: impl Iterator<Item = String>,
+) -> Result<Vec<MainEntity>, crate::Error> {
+    let mut nodes = Vec::new();
+
+    for mbid in mbids {
+        if let Some(release) =
+            Release::get_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db, &mbid).await?
+        {
+            nodes.push(MainEntity::Release(release));
+        }
+    }
+
+    Ok(nodes)
 }
 
 // #[cfg(test)]
 // mod tests {
 //     use crate::tools::musicbrainz::clippy::mb_clippy;
-//     use crate::utils::whitelist_blacklist::WhitelistBlacklist;
-
+//
 //     #[tokio::test]
 //     async fn mb_clippy_test() {
 //         mb_clippy(
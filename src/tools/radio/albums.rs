@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use alistral_core::datastructures::entity_with_listens::release::collection::ReleaseWithListensCollection;
+use alistral_core::datastructures::entity_with_listens::release_group::collection::ReleaseGroupWithListensCollection;
+use alistral_core::datastructures::entity_with_listens::release_group::ReleaseGroupWithListens;
+use alistral_core::datastructures::listen_collection::traits::ListenCollectionReadable as _;
+use alistral_core::datastructures::listen_collection::ListenCollection;
+use chrono::Duration;
+use chrono::Utc;
+use interzic::models::playlist_stub::PlaylistStub;
+use itertools::Itertools as _;
+use musicbrainz_db_lite::models::musicbrainz::recording::Recording;
+use musicbrainz_db_lite::models::musicbrainz::release::Release;
+use tracing::info;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::api::listenbrainz::global_listen_counts::get_global_release_group_listen_counts;
+use crate::database::listenbrainz::listens::ListenFetchQuery;
+use crate::database::listenbrainz::listens::ListenFetchQueryReturn;
+use crate::database::listenbrainz::prefetching::prefetch_releases;
+use crate::datastructures::radio::collector::RadioCollector;
+use crate::datastructures::radio::exporter::RadioExporter;
+use crate::datastructures::radio::seeders::listens::ListenSeeder;
+use crate::datastructures::radio::sorters::album::album_listen_rate_sorter;
+use crate::datastructures::radio::sorters::album::album_overdue_sorter;
+use crate::datastructures::radio::sorters::album::album_underrated_sorter;
+use crate::datastructures::radio::sorters::album::AlbumSorter;
+use crate::models::error::ResultTEExt as _;
+use crate::tools::radio::convert_recordings;
+
+/// Create a radio made of whole albums, in the order of their tracklist
+#[expect(clippy::too_many_arguments)]
+pub async fn album_radio(
+    conn: &mut sqlx::SqliteConnection,
+    seeder: ListenSeeder,
+    token: Option<&str>,
+    sorter: AlbumSorter,
+    min_listens: Option<u64>,
+    cooldown: u64,
+    collector: RadioCollector,
+    exporter: RadioExporter,
+    temperature: Option<f64>,
+) -> Result<(), crate::Error> {
+    let username = seeder.username().clone();
+
+    info!("[Seeding] Getting listens");
+    let recordings = seeder
+        .seed(conn)
+        .await
+        .expect_fatal("Couldn't find seed listens");
+
+    let listens = ListenCollection::new(
+        recordings
+            .iter()
+            .flat_map(|r| r.listens().iter().cloned())
+            .collect_vec(),
+    );
+
+    info!("[Seeding] Compiling album listens");
+    let (albums, main_releases) = get_albums(conn, listens).await?;
+
+    info!("[Filter] Filtering minimum listen count");
+    info!("[Filter] Filtering listen cooldown");
+    let min_listens = min_listens.unwrap_or(3);
+    let albums = albums
+        .into_iter()
+        .filter(|album| album.listen_count() as u64 >= min_listens)
+        .filter(|album| {
+            album
+                .latest_listen_date()
+                .is_none_or(|date| date + Duration::hours(cooldown as i64) <= Utc::now())
+        })
+        .collect_vec();
+
+    info!("[Sorting] Sorting albums");
+    let albums = match sorter {
+        AlbumSorter::Overdue => album_overdue_sorter(albums, temperature),
+        AlbumSorter::Rate => album_listen_rate_sorter(albums, temperature),
+        AlbumSorter::Underrated => {
+            // Get the all time listens
+            let user_listens = ListenFetchQuery::builder()
+                .returns(ListenFetchQueryReturn::Mapped)
+                .user(username.to_string())
+                .build()
+                .fetch(conn)
+                .await
+                .expect_fatal("Couldn't fetch the new listens");
+
+            let user_albums = ReleaseGroupWithListensCollection::from_listencollection(
+                conn,
+                &ALISTRAL_CLIENT,
+                user_listens,
+            )
+            .await?;
+
+            info!("[Seeding] Getting global listen counts");
+            let album_ids = albums
+                .iter()
+                .map(|a| a.release_group().mbid.clone())
+                .collect_vec();
+            let global_listen_counts = get_global_release_group_listen_counts(&album_ids).await?;
+
+            album_underrated_sorter(albums, &user_albums, global_listen_counts, temperature)
+        }
+    };
+
+    info!("[Finalising] Creating radio playlist");
+    let mut playlist = collector.new_playlist();
+    for album in albums {
+        let Some(release) = main_releases.get(&album.release_group().mbid) else {
+            continue;
+        };
+
+        let tracks = get_release_tracklist(conn, release).await?;
+        if tracks.is_empty() {
+            continue;
+        }
+
+        if playlist.push_album(tracks) {
+            break;
+        }
+    }
+    let collected = playlist.finish();
+
+    info!("[Sending] Sending radio playlist");
+    let playlist = PlaylistStub {
+        title: format!("Radio: Albums #{}", exporter.get_playlist_number()),
+        description: "Automatically generated by: https://github.com/RustyNova016/Alistral"
+            .to_string(),
+        recordings: convert_recordings(conn, collected)
+            .await
+            .expect_fatal("Couldn't convert recordings for playlist"),
+    };
+
+    exporter
+        .export(playlist, Some(username), token)
+        .await
+        .expect_fatal("Couldn't send the playlist");
+
+    Ok(())
+}
+
+/// Group the listens by album.
+///
+/// Also returns the MBID of the most listened release of each album, to get the tracklist from
+async fn get_albums(
+    conn: &mut sqlx::SqliteConnection,
+    listens: ListenCollection,
+) -> Result<(Vec<ReleaseGroupWithListens>, HashMap<String, String>), crate::Error> {
+    let releases =
+        ReleaseWithListensCollection::from_listencollection(conn, &ALISTRAL_CLIENT, listens)
+            .await?;
+
+    let release_refs = releases.iter_entities().collect_vec();
+    prefetch_releases(conn, &release_refs).await?;
+    let results = Release::get_release_groups_as_batch(conn, &release_refs).await?;
+
+    let mut albums = ReleaseGroupWithListensCollection::new();
+    let mut main_releases: HashMap<String, (String, usize)> = HashMap::new();
+
+    for (_, (release, release_groups)) in results {
+        let Some(release_listens) = releases.get_by_id(release.id) else {
+            continue;
+        };
+        let listen_count = release_listens.listen_count();
+
+        for release_group in release_groups {
+            let main = main_releases
+                .entry(release_group.mbid.clone())
+                .or_insert_with(|| (release.mbid.clone(), listen_count));
+
+            if main.1 < listen_count {
+                *main = (release.mbid.clone(), listen_count);
+            }
+
+            albums.insert_or_merge_entity(ReleaseGroupWithListens::new(
+                release_group,
+                release_listens.listens().clone(),
+            ));
+        }
+    }
+
+    let main_releases = main_releases
+        .into_iter()
+        .map(|(album, (release, _))| (album, release))
+        .collect();
+
+    Ok((albums.into_iter().collect_vec(), main_releases))
+}
+
+/// Get the recordings of a release, in the order of its tracklist
+async fn get_release_tracklist(
+    conn: &mut sqlx::SqliteConnection,
+    release_mbid: &str,
+) -> Result<Vec<Recording>, crate::Error> {
+    let Some(release) =
+        Release::get_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db, release_mbid).await?
+    else {
+        return Ok(Vec::new());
+    };
+
+    Ok(release
+        .get_recordings_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db)
+        .await?)
+}
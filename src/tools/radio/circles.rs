@@ -1,6 +1,3 @@
-use async_fn_stream::try_fn_stream;
-use futures::pin_mut;
-use futures::Stream;
 use futures::TryStreamExt;
 use interzic::models::playlist_stub::PlaylistStub;
 use itertools::Itertools;
@@ -13,21 +10,19 @@ use tracing::warn;
 
 use crate::api::clients::ALISTRAL_CLIENT;
 use crate::datastructures::radio::collector::RadioCollector;
+use crate::datastructures::radio::exporter::RadioExporter;
 use crate::datastructures::radio::seeders::listens::ListenSeeder;
-use crate::models::cli::radio::RadioExportTarget;
-use crate::models::data_storage::DataStorage;
 use crate::models::error::ResultTEExt as _;
 use crate::tools::radio::convert_recordings;
 use crate::utils::cli::display::ArtistExt;
-use crate::utils::data_file::DataFile as _;
 
 pub async fn create_radio_mix(
     conn: &mut sqlx::SqliteConnection,
     seeder: ListenSeeder,
-    token: String,
+    token: Option<String>,
     unlistened: bool,
     collector: RadioCollector,
-    target: RadioExportTarget,
+    exporter: RadioExporter,
 ) {
     let username = seeder.username().clone();
 
@@ -39,26 +34,16 @@ pub async fn create_radio_mix(
 
     let recordings = recordings_with_listens.iter_entities().collect_vec();
 
-    let radio = RadioCircle::new(unlistened);
+    let mut radio = RadioCircle::new(unlistened);
 
-    let collected = {
-        let radio_stream = radio.into_stream(conn, recordings);
-
-        info!("[Finalising] Creating radio playlist");
-        pin_mut!(radio_stream);
-
-        collector
-            .try_collect(radio_stream)
-            .await
-            .expect_fatal("Error while generating the playlist")
-    };
+    info!("[Finalising] Creating radio playlist");
+    let collected = radio
+        .collect(conn, recordings, &collector)
+        .await
+        .expect_fatal("Error while generating the playlist");
 
-    let counter = DataStorage::load().expect_fatal("Couldn't load data storage");
     let playlist = PlaylistStub {
-        title: format!(
-            "Radio: Circles #{}",
-            counter.write().unwrap().incr_playlist_count()
-        ),
+        title: format!("Radio: Circles #{}", exporter.get_playlist_number()),
         description: "Automatically generated by: https://github.com/RustyNova016/Alistral"
             .to_string(),
         recordings: convert_recordings(conn, collected)
@@ -66,8 +51,8 @@ pub async fn create_radio_mix(
             .expect_fatal("Couldn't convert recordings for playlist"),
     };
 
-    target
-        .export(playlist, Some(username), Some(&token))
+    exporter
+        .export(playlist, Some(username), token.as_deref())
         .await
         .expect_fatal("Couldn't send the playlist");
 }
@@ -177,18 +162,24 @@ impl RadioCircle {
         }
     }
 
-    pub fn into_stream<'conn, 'recordings>(
-        mut self,
-        conn: &'conn mut sqlx::SqliteConnection,
-        recordings: Vec<&'recordings Recording>,
-    ) -> impl Stream<Item = Result<Recording, crate::Error>> + use<'conn, 'recordings> {
-        try_fn_stream(|emitter| async move {
-            while let Some(val) = self.get_random_item(conn, recordings.clone()).await? {
-                emitter.emit(val).await;
+    /// Fill the playlist with random items.
+    ///
+    /// This doesn't use a stream, as the collector needs the connection too
+    pub async fn collect(
+        &mut self,
+        conn: &mut sqlx::SqliteConnection,
+        recordings: Vec<&Recording>,
+        collector: &RadioCollector,
+    ) -> Result<Vec<Recording>, crate::Error> {
+        let mut playlist = collector.new_playlist();
+
+        while let Some(val) = self.get_random_item(conn, recordings.clone()).await? {
+            if playlist.push(conn, val).await? {
+                break;
             }
+        }
 
-            Ok(())
-        })
+        playlist.finish_with_transitions(conn).await
     }
 }
 impl Default for RadioCircle {
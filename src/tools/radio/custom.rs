@@ -0,0 +1,93 @@
+use core::pin::Pin;
+
+use alistral_core::datastructures::entity_with_listens::recording::RecordingWithListens;
+use futures::stream;
+use futures::Stream;
+use futures::StreamExt;
+use interzic::models::playlist_stub::PlaylistStub;
+use rust_decimal::Decimal;
+use tracing::info;
+
+use crate::datastructures::radio::collector::RadioCollector;
+use crate::datastructures::radio::exporter::RadioExporter;
+use crate::datastructures::radio::recipe::RadioRecipe;
+use crate::datastructures::radio::report::RadioReport;
+use crate::datastructures::radio::seeders::listens::ListenSeeder;
+use crate::models::error::ResultTEExt as _;
+use crate::tools::radio::convert_recordings;
+
+pub async fn custom_radio(
+    conn: &mut sqlx::SqliteConnection,
+    recipe: RadioRecipe,
+    seeder: ListenSeeder,
+    token: Option<&str>,
+    collector: RadioCollector,
+    mut exporter: RadioExporter,
+    temperature: Option<f64>,
+) -> Result<(), crate::Error> {
+    let username = seeder.username().clone();
+
+    info!("[Seeding] Getting listens");
+    let recordings = seeder
+        .seed(conn)
+        .await
+        .expect_fatal("Couldn't find seed listens");
+
+    let mut report = exporter
+        .needs_report()
+        .then(|| RadioReport::new(&username, &recordings, recipe.filters.clone()));
+
+    let mut recordings: Pin<Box<dyn Stream<Item = RecordingWithListens>>> =
+        Box::pin(recordings.into_stream());
+
+    for filter in &recipe.filters {
+        info!("[Filter] Applying filter `{}`", filter.get_name());
+        recordings = filter.filter(&username, recordings);
+    }
+
+    let recordings = recipe
+        .sorter
+        .sort(conn, &username, recordings.collect().await, temperature)
+        .await?;
+
+    let recordings: Pin<Box<dyn Stream<Item = (RecordingWithListens, Decimal)>>> = match &mut report
+    {
+        Some(report) => {
+            // The report needs the rank of every recording, not only the ones collected
+            let sorted = recordings.collect::<Vec<_>>().await;
+            report.set_sorted(&sorted);
+            Box::pin(stream::iter(sorted))
+        }
+        None => recordings,
+    };
+
+    info!("[Finalising] Creating radio playlist");
+    let collected = collector
+        .collect(conn, recordings.map(|(r, _)| r.recording().clone()))
+        .await?;
+
+    if let Some(report) = report {
+        exporter.set_report(report);
+    }
+
+    info!("[Sending] Sending radio playlist");
+    let playlist = PlaylistStub {
+        title: format!(
+            "Radio: {} #{}",
+            recipe.name.as_deref().unwrap_or("Custom"),
+            exporter.get_playlist_number()
+        ),
+        description: "Automatically generated by: https://github.com/RustyNova016/Alistral"
+            .to_string(),
+        recordings: convert_recordings(conn, collected)
+            .await
+            .expect_fatal("Couldn't convert recordings for playlist"),
+    };
+
+    exporter
+        .export(playlist, Some(username), token)
+        .await
+        .expect_fatal("Couldn't send the playlist");
+
+    Ok(())
+}
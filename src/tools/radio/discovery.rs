@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use alistral_core::datastructures::entity_with_listens::recording::collection::RecordingWithListensCollection;
+use alistral_core::datastructures::listen_collection::traits::ListenCollectionReadable as _;
+use futures::TryStreamExt as _;
+use interzic::models::playlist_stub::PlaylistStub;
+use itertools::Itertools as _;
+use musicbrainz_db_lite::models::musicbrainz::artist::Artist;
+use musicbrainz_db_lite::models::musicbrainz::recording::Recording;
+use rand::seq::SliceRandom as _;
+use rand::thread_rng;
+use tracing::info;
+use tracing::instrument;
+use tuillez::pg_counted;
+use tuillez::pg_inc;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::database::listenbrainz::prefetching::fetch_recordings_as_complete;
+use crate::datastructures::radio::collector::RadioCollector;
+use crate::datastructures::radio::exporter::RadioExporter;
+use crate::datastructures::radio::seeders::listens::ListenSeeder;
+use crate::models::error::ResultTEExt as _;
+use crate::tools::radio::convert_recordings;
+use crate::utils::extensions::db_lite_ext::RelationRecordingArtistExt as _;
+
+/// How many of the user's top artists are explored
+const TOP_ARTIST_COUNT: usize = 25;
+
+/// How many of the user's top recordings are explored
+const TOP_RECORDING_COUNT: usize = 50;
+
+/// The artist to artist relationships to follow, with how strongly they link the artists
+const ARTIST_LINKS: [(&str, f64); 3] = [
+    ("member of band", 1.0),
+    ("subgroup", 0.8),
+    ("collaboration", 0.8),
+];
+
+/// The recording to artist relationships to follow, with how strongly they link the artists
+const RECORDING_LINKS: [(&str, f64); 2] = [("remixer", 0.6), ("producer", 0.5)];
+
+pub async fn discovery_radio(
+    conn: &mut sqlx::SqliteConnection,
+    seeder: ListenSeeder,
+    token: Option<&str>,
+    collector: RadioCollector,
+    exporter: RadioExporter,
+) -> Result<(), crate::Error> {
+    let username = seeder.username().clone();
+
+    info!("[Seeding] Getting listens");
+    let recordings = seeder
+        .seed(conn)
+        .await
+        .expect_fatal("Couldn't find seed listens");
+
+    info!("[Seeding] Exploring the relationships of your top artists");
+    let mut radio = RadioDiscovery::new(conn, &recordings).await?;
+
+    info!("[Finalising] Creating radio playlist");
+    let collected = radio.collect(conn, &collector).await?;
+
+    info!("[Sending] Sending radio playlist");
+    let playlist = PlaylistStub {
+        title: format!(
+            "Radio: Discovery #{}",
+            exporter.get_playlist_number()
+        ),
+        description: format!("Artists related to the ones {username} listens to, but never listened yet. They are members, collaborators, remixers or producers of {username}'s top artists and recordings.<br>
+        <br>
+        Automatically generated by: https://github.com/RustyNova016/Alistral"),
+        recordings: convert_recordings(conn, collected)
+            .await
+            .expect_fatal("Couldn't convert recordings for playlist"),
+    };
+
+    exporter
+        .export(playlist, Some(username), token)
+        .await
+        .expect_fatal("Couldn't send the playlist");
+
+    Ok(())
+}
+
+/// A radio suggesting artists that are related to the user's top artists, but that the user never listened to
+#[derive(Debug)]
+pub struct RadioDiscovery {
+    /// The MBIDs of the unlistened related artists, with how strongly they are connected to the user's listens
+    candidates: Vec<(String, f64)>,
+
+    /// The recordings already put in the playlist
+    picked: HashSet<String>,
+}
+
+impl RadioDiscovery {
+    #[instrument(skip(conn, recordings), fields(indicatif.pb_show = tracing::field::Empty))]
+    pub async fn new(
+        conn: &mut sqlx::SqliteConnection,
+        recordings: &RecordingWithListensCollection,
+    ) -> Result<Self, crate::Error> {
+        let recording_refs = recordings.iter_entities().collect_vec();
+        fetch_recordings_as_complete(conn, &recording_refs).await?;
+
+        // Count the listens of each artist
+        let mut listened_artists: HashMap<String, u64> = HashMap::new();
+        let results = Recording::get_artist_from_credits_as_batch(conn, &recording_refs).await?;
+        for (_, (recording, artists)) in results {
+            let listens = recordings
+                .get_by_id(recording.id)
+                .map(|r| r.listen_count() as u64)
+                .unwrap_or(0);
+
+            for artist in artists {
+                *listened_artists.entry(artist.mbid.clone()).or_default() += listens;
+            }
+        }
+
+        let top_artists = listened_artists
+            .iter()
+            .sorted_by_key(|(_, listens)| core::cmp::Reverse(**listens))
+            .take(TOP_ARTIST_COUNT)
+            .collect_vec();
+
+        let top_recordings = recordings
+            .iter()
+            .sorted_by_key(|r| core::cmp::Reverse(r.listen_count()))
+            .take(TOP_RECORDING_COUNT)
+            .collect_vec();
+
+        pg_counted!(
+            top_artists.len() + top_recordings.len(),
+            "Exploring relationships"
+        );
+
+        // The relations are read from the local database, so only the missing entities are fetched
+        let mut scores: HashMap<String, f64> = HashMap::new();
+        for (mbid, listens) in top_artists {
+            let Some(artist) =
+                Artist::get_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db, mbid).await?
+            else {
+                pg_inc!();
+                continue;
+            };
+            artist
+                .fetch_if_incomplete(conn, &ALISTRAL_CLIENT.musicbrainz_db)
+                .await?;
+
+            for relation in artist.get_artist_relations(conn).await? {
+                if let Some(weight) = get_link_weight(&ARTIST_LINKS, &relation.relation_type) {
+                    let related = relation.get_other_entity(conn, artist.id).await?;
+                    *scores.entry(related.mbid).or_default() += *listens as f64 * weight;
+                }
+            }
+            pg_inc!();
+        }
+
+        for recording in top_recordings {
+            let listens = recording.listen_count() as f64;
+
+            for relation in recording.recording().get_artist_relations(conn).await? {
+                if let Some(weight) = get_link_weight(&RECORDING_LINKS, &relation.relation_type) {
+                    if let Some(related) = relation.get_artist(conn).await? {
+                        *scores.entry(related.mbid).or_default() += listens * weight;
+                    }
+                }
+            }
+            pg_inc!();
+        }
+
+        // Only keep the artists the user never listened to
+        let candidates = scores
+            .into_iter()
+            .filter(|(mbid, score)| !listened_artists.contains_key(mbid) && *score > 0.0)
+            .collect_vec();
+
+        info!("Found {} related artists to discover", candidates.len());
+
+        Ok(Self {
+            candidates,
+            picked: HashSet::new(),
+        })
+    }
+
+    /// Fill the playlist with recordings of the related artists
+    pub async fn collect(
+        &mut self,
+        conn: &mut sqlx::SqliteConnection,
+        collector: &RadioCollector,
+    ) -> Result<Vec<Recording>, crate::Error> {
+        let mut playlist = collector.new_playlist();
+
+        while let Some(index) = self.pick_artist() {
+            let mbid = self.candidates[index].0.clone();
+
+            match self.get_random_recording(conn, &mbid).await? {
+                Some(recording) => {
+                    // Lower the weight of the artist to give a chance to the others
+                    self.candidates[index].1 /= 2.0;
+                    self.picked.insert(recording.mbid.clone());
+
+                    if playlist.push(conn, recording).await? {
+                        break;
+                    }
+                }
+                None => {
+                    self.candidates.remove(index);
+                }
+            }
+        }
+
+        playlist.finish_with_transitions(conn).await
+    }
+
+    /// Pick a random artist, weighted by its connection score
+    fn pick_artist(&self) -> Option<usize> {
+        self.candidates
+            .iter()
+            .enumerate()
+            .collect_vec()
+            .choose_weighted(&mut thread_rng(), |(_, (_, score))| *score)
+            .ok()
+            .map(|(index, _)| *index)
+    }
+
+    async fn get_random_recording(
+        &self,
+        conn: &mut sqlx::SqliteConnection,
+        artist_mbid: &str,
+    ) -> Result<Option<Recording>, crate::Error> {
+        let Some(artist) =
+            Artist::get_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db, artist_mbid).await?
+        else {
+            return Ok(None);
+        };
+
+        let mut recordings: Vec<Recording> = artist
+            .browse_or_fetch_artist_recordings(conn)
+            .try_collect()
+            .await?;
+
+        recordings.shuffle(&mut thread_rng());
+
+        Ok(recordings
+            .into_iter()
+            .find(|recording| !self.picked.contains(&recording.mbid)))
+    }
+}
+
+/// Get how strongly a relationship type links the artists, if it should be followed
+fn get_link_weight(links: &[(&str, f64)], relation_type: &str) -> Option<f64> {
+    links
+        .iter()
+        .find(|(link, _)| *link == relation_type)
+        .map(|(_, weight)| *weight)
+}
@@ -0,0 +1,105 @@
+use core::pin::Pin;
+
+use alistral_core::datastructures::entity_with_listens::recording::RecordingWithListens;
+use chrono::Duration;
+use futures::stream;
+use futures::Stream;
+use futures::StreamExt;
+use interzic::models::playlist_stub::PlaylistStub;
+use itertools::Itertools;
+use tracing::info;
+
+use crate::datastructures::radio::collector::RadioCollector;
+use crate::datastructures::radio::exporter::RadioExporter;
+use crate::datastructures::radio::filters::cooldown::cooldown_filter;
+use crate::datastructures::radio::filters::hated::hated_filter;
+use crate::datastructures::radio::filters::min_listens::min_listen_filter;
+use crate::datastructures::radio::filters::timeouts::timeout_filter;
+use crate::datastructures::radio::seeders::listens::ListenSeeder;
+use crate::datastructures::radio::sorters::group::group_sorter;
+use crate::models::error::ResultTEExt as _;
+use crate::tools::radio::convert_recordings;
+
+/// Create a radio from the listens of multiple users.
+///
+/// The seeder is used for each user, only replacing its username.
+#[expect(clippy::too_many_arguments)]
+pub async fn group_radio(
+    conn: &mut sqlx::SqliteConnection,
+    seeder: ListenSeeder,
+    usernames: Vec<String>,
+    min_users: usize,
+    min_listens: Option<u64>,
+    cooldown: u64,
+    collector: RadioCollector,
+    token: Option<&str>,
+    exporter: RadioExporter,
+    temperature: Option<f64>,
+    keep_hated: bool,
+) -> color_eyre::Result<()> {
+    let Some(owner) = usernames.first().cloned() else {
+        return Ok(());
+    };
+
+    let mut user_recordings = Vec::with_capacity(usernames.len());
+    for username in &usernames {
+        info!("[Seeding] Getting listens of {username}");
+        let mut user_seeder = seeder.clone();
+        user_seeder.username = username.clone();
+
+        let recordings = user_seeder
+            .seed(conn)
+            .await
+            .expect_fatal("Couldn't find seed listens");
+
+        let recordings = min_listen_filter(recordings.into_stream(), min_listens.unwrap_or(1));
+        let recordings = cooldown_filter(recordings, Duration::hours(cooldown as i64));
+        let recordings = timeout_filter(recordings);
+
+        user_recordings.push(recordings.collect::<Vec<_>>().await);
+    }
+
+    info!("[Sorting] Calculating group scores");
+    let recordings = group_sorter(
+        user_recordings,
+        min_users.clamp(1, usernames.len()),
+        temperature,
+    );
+
+    // A recording hated by any member of the group is removed
+    let mut recordings: Pin<Box<dyn Stream<Item = RecordingWithListens>>> =
+        Box::pin(stream::iter(recordings));
+    if !keep_hated {
+        info!("[Filter] Filtering hated recordings");
+        for username in &usernames {
+            recordings = Box::pin(hated_filter(recordings, username.clone()));
+        }
+    }
+
+    info!("[Finalising] Creating radio playlist");
+    let collected = collector
+        .collect(conn, recordings.map(|r| r.recording().clone()))
+        .await?;
+
+    info!("[Sending] Sending radio playlist to listenbrainz");
+    let playlist = PlaylistStub {
+        title: format!("Radio: Group #{}", exporter.get_playlist_number()),
+        description: format!(
+            "
+        A radio made from the listened recordings of {}</br>
+
+        Automatically generated by: https://github.com/RustyNova016/Alistral",
+            usernames.iter().join(", ")
+        ),
+        recordings: convert_recordings(conn, collected)
+            .await
+            .expect_fatal("Couldn't convert recordings for playlist"),
+    };
+
+    exporter
+        .export(playlist, Some(owner), token)
+        .await
+        .expect_fatal("Couldn't send the playlist");
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use chrono::DateTime;
+use chrono::Local;
+use interzic::models::playlist_history::PlaylistHistory;
+use interzic::models::playlist_stub::PlaylistStub;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::datastructures::radio::exporter::RadioExporter;
+
+/// Print the latest generated radios
+pub async fn list_radio_history(limit: u64) -> Result<(), crate::Error> {
+    let db = &ALISTRAL_CLIENT.interzic.database_client;
+    let history = PlaylistHistory::find_latest(db, limit).await?;
+
+    if history.is_empty() {
+        println!("No radio has been generated yet");
+        return Ok(());
+    }
+
+    for playlist in history {
+        let recording_count = playlist.get_recordings(db).await?.len();
+        let date = DateTime::from_timestamp(playlist.created_at, 0)
+            .map(|date| {
+                date.with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default();
+
+        println!(
+            "[{}] {date} - {} ({} recordings, {} radio, exported to {})",
+            playlist.id, playlist.title, recording_count, playlist.kind, playlist.target
+        );
+    }
+
+    Ok(())
+}
+
+/// Export a radio from the history again
+pub async fn export_radio_history(
+    id: i64,
+    exporter: RadioExporter,
+    username: Option<String>,
+    token: Option<&str>,
+) -> Result<(), crate::Error> {
+    let db = &ALISTRAL_CLIENT.interzic.database_client;
+    let playlist = PlaylistHistory::find_by_id(db, id)
+        .await?
+        .ok_or(crate::Error::MissingRadioHistoryError(id))?;
+
+    let stub = PlaylistStub {
+        recordings: playlist.get_recordings(db).await?,
+        title: playlist.title,
+        description: playlist.description,
+    };
+
+    exporter.export(stub, username, token).await
+}
@@ -1,51 +1,48 @@
 use interzic::models::messy_recording::MessyRecording;
-use interzic::models::playlist_stub::PlaylistStub;
-use interzic::models::services::listenbrainz::Listenbrainz;
-use interzic::models::services::youtube::Youtube;
 use musicbrainz_db_lite::models::musicbrainz::recording::Recording;
 use tracing::instrument;
 use tracing::Span;
 use tracing_indicatif::span_ext::IndicatifSpanExt as _;
 
 use crate::api::clients::ALISTRAL_CLIENT;
-use crate::models::cli::radio::RadioExportTarget;
+use crate::datastructures::radio::filters::RadioFilters;
 
+pub mod albums;
 pub mod circles;
+pub mod custom;
+pub mod discovery;
+pub mod group;
+pub mod history;
 pub mod listen_rate;
 pub mod overdue;
 pub mod shared;
 pub mod underrated;
 
-impl RadioExportTarget {
-    pub async fn export(
-        &self,
-        playlist: PlaylistStub,
-        username: Option<String>,
-        token: Option<&str>,
-    ) -> Result<(), crate::Error> {
-        match self {
-            RadioExportTarget::Listenbrainz => {
-                Listenbrainz::create_playlist(
-                    &ALISTRAL_CLIENT.interzic,
-                    playlist,
-                    username.ok_or(crate::Error::MissingPlaylistUserDataError(
-                        "username".to_string(),
-                    ))?,
-                    token.ok_or(crate::Error::MissingPlaylistUserDataError(
-                        "token".to_string(),
-                    ))?,
-                )
-                .await?;
-            }
-            Self::Youtube => {
-                let _playlist_id =
-                    Youtube::create_playlist(&ALISTRAL_CLIENT.interzic, playlist).await?;
-                //TODO: display url after export
-            }
-        }
+/// Get the usual filters of the radios based on listens
+pub fn get_listen_filters(
+    min_listens: Option<u64>,
+    cooldown: u64,
+    suggestion_cooldown: Option<u64>,
+    keep_hated: bool,
+) -> Vec<RadioFilters> {
+    let mut filters = Vec::new();
+
+    if let Some(min) = min_listens {
+        filters.push(RadioFilters::MinListens(min));
+    }
+
+    filters.push(RadioFilters::Cooldown(cooldown));
+    filters.push(RadioFilters::Timeouts);
 
-        Ok(())
+    if !keep_hated {
+        filters.push(RadioFilters::Hated);
     }
+
+    if let Some(days) = suggestion_cooldown {
+        filters.push(RadioFilters::RecentlySuggested(days));
+    }
+
+    filters
 }
 
 #[instrument(fields(indicatif.pb_show = tracing::field::Empty))]
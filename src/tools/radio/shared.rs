@@ -1,22 +1,25 @@
+use core::pin::Pin;
+
+use alistral_core::datastructures::entity_with_listens::recording::RecordingWithListens;
 use chrono::Duration;
 use futures::stream;
+use futures::Stream;
 use futures::StreamExt;
 use interzic::models::playlist_stub::PlaylistStub;
 use itertools::Itertools;
 use tracing::info;
 
 use crate::datastructures::radio::collector::RadioCollector;
+use crate::datastructures::radio::exporter::RadioExporter;
 use crate::datastructures::radio::filters::booleans::and_filter;
 use crate::datastructures::radio::filters::cooldown::cooldown_filter;
+use crate::datastructures::radio::filters::hated::hated_filter;
 use crate::datastructures::radio::filters::min_listens::min_listen_filter;
 use crate::datastructures::radio::filters::timeouts::timeout_filter;
 use crate::datastructures::radio::seeders::listens::ListenSeeder;
 use crate::datastructures::radio::sorters::shared::shared_listens_sorter;
-use crate::models::cli::radio::RadioExportTarget;
-use crate::models::data_storage::DataStorage;
 use crate::models::error::ResultTEExt as _;
 use crate::tools::radio::convert_recordings;
-use crate::utils::data_file::DataFile as _;
 
 #[expect(clippy::too_many_arguments)]
 pub async fn shared_radio(
@@ -26,8 +29,9 @@ pub async fn shared_radio(
     min_listens: Option<u64>,
     cooldown: u64,
     collector: RadioCollector,
-    token: &str,
-    target: RadioExportTarget,
+    token: Option<&str>,
+    exporter: RadioExporter,
+    keep_hated: bool,
 ) -> color_eyre::Result<()> {
     let username = seeder.username().clone();
 
@@ -55,6 +59,13 @@ pub async fn shared_radio(
     info!("[Filter] Filtering listen timeouts");
     let recordings = timeout_filter(recordings);
 
+    let recordings: Pin<Box<dyn Stream<Item = RecordingWithListens>>> = if keep_hated {
+        Box::pin(recordings)
+    } else {
+        info!("[Filter] Filtering hated recordings");
+        Box::pin(hated_filter(recordings, username.clone()))
+    };
+
     info!("[Filter] Filtering by other user");
     let recordings = and_filter(recordings, other_recordings.clone());
 
@@ -63,16 +74,15 @@ pub async fn shared_radio(
 
     info!("[Finalising] Creating radio playlist");
     let collected = collector
-        .collect(stream::iter(recordings).map(|r| r.recording().clone()))
-        .await;
+        .collect(
+            conn,
+            stream::iter(recordings).map(|r| r.recording().clone()),
+        )
+        .await?;
 
     info!("[Sending] Sending radio playlist to listenbrainz");
-    let counter = DataStorage::load().expect_fatal("Couldn't load data storage");
     let playlist = PlaylistStub {
-        title: format!(
-            "Radio: Shared listens #{}",
-            counter.write().unwrap().incr_playlist_count()
-        ),
+        title: format!("Radio: Shared listens #{}", exporter.get_playlist_number()),
         description: format!(
             "
         A radio made from the listened recordings of {username} and {other_user}</br>
@@ -84,8 +94,8 @@ pub async fn shared_radio(
             .expect_fatal("Couldn't convert recordings for playlist"),
     };
 
-    target
-        .export(playlist, Some(username), Some(token))
+    exporter
+        .export(playlist, Some(username), token)
         .await
         .expect_fatal("Couldn't send the playlist");
 
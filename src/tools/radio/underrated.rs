@@ -1,5 +1,9 @@
+use core::pin::Pin;
+
 use alistral_core::datastructures::entity_with_listens::recording::collection::RecordingWithListensCollection;
+use alistral_core::datastructures::entity_with_listens::recording::RecordingWithListens;
 use futures::stream;
+use futures::Stream;
 use futures::StreamExt;
 use interzic::models::playlist_stub::PlaylistStub;
 use itertools::Itertools;
@@ -10,20 +14,22 @@ use crate::api::listenbrainz::global_listen_counts::get_global_listen_counts;
 use crate::database::listenbrainz::listens::ListenFetchQuery;
 use crate::database::listenbrainz::listens::ListenFetchQueryReturn;
 use crate::datastructures::radio::collector::RadioCollector;
+use crate::datastructures::radio::exporter::RadioExporter;
+use crate::datastructures::radio::filters::RadioFilters;
+use crate::datastructures::radio::report::RadioReport;
 use crate::datastructures::radio::seeders::listens::ListenSeeder;
 use crate::datastructures::radio::sorters::underrated::underrated_sorter;
-use crate::models::cli::radio::RadioExportTarget;
-use crate::models::data_storage::DataStorage;
 use crate::models::error::ResultTEExt as _;
 use crate::tools::radio::convert_recordings;
-use crate::utils::data_file::DataFile as _;
 
 pub async fn underrated_mix(
     conn: &mut sqlx::SqliteConnection,
     seeder: ListenSeeder,
     collector: RadioCollector,
-    token: &str,
-    target: RadioExportTarget,
+    token: Option<&str>,
+    mut exporter: RadioExporter,
+    temperature: Option<f64>,
+    keep_hated: bool,
 ) -> Result<(), crate::Error> {
     let username = seeder.username().clone();
 
@@ -35,6 +41,16 @@ pub async fn underrated_mix(
         .await
         .expect_fatal("Couldn't find seed listens");
 
+    let filters = if keep_hated {
+        Vec::new()
+    } else {
+        vec![RadioFilters::Hated]
+    };
+
+    let mut report = exporter
+        .needs_report()
+        .then(|| RadioReport::new(&username, &recordings, filters.clone()));
+
     // Get the all time listens
     let user_listens = ListenFetchQuery::builder()
         .returns(ListenFetchQueryReturn::Mapped)
@@ -50,36 +66,50 @@ pub async fn underrated_mix(
 
     // Get the global listen count
     info!("[Seeding] Getting global listen counts");
+    let mut recordings: Pin<Box<dyn Stream<Item = RecordingWithListens>>> =
+        Box::pin(recordings.into_stream());
+    for filter in &filters {
+        info!("[Filter] Applying filter `{}`", filter.get_name());
+        recordings = filter.filter(&username, recordings);
+    }
+    let recordings = recordings.collect::<Vec<_>>().await;
+
     let recording_ids = recordings
-        .iter_entities()
-        .map(|r| r.mbid.to_string())
+        .iter()
+        .map(|r| r.recording().mbid.to_string())
         .collect_vec();
     let global_listen_counts = get_global_listen_counts(&recording_ids).await?;
 
     info!("[Sorting] Calculating underated scores");
-    let sorted = underrated_sorter(
-        recordings.into_iter().collect_vec(),
-        &user_listens,
-        global_listen_counts,
-    );
+    let sorted = underrated_sorter(recordings, &user_listens, global_listen_counts, temperature);
+
+    if let Some(report) = &mut report {
+        report.set_sorted(&sorted);
+    }
 
     info!("[Finalising] Creating radio playlist");
     let collected = collector
-        .collect(stream::iter(sorted).map(|r| r.recording().clone()))
-        .await;
+        .collect(
+            conn,
+            stream::iter(sorted).map(|(r, _)| r.recording().clone()),
+        )
+        .await?;
+
+    if let Some(report) = report {
+        exporter.set_report(report);
+    }
 
     info!("[Sending] Sending radio playlist to listenbrainz");
-    let counter = DataStorage::load().expect_fatal("Couldn't load data storage");
     let playlist = PlaylistStub {
         title: format!(
             "Radio: Underrated recordings #{}",
-            counter.write().unwrap().incr_playlist_count()
+            exporter.get_playlist_number()
         ),
         description:  format!("A playlist containing all the tracks that {username} listen to, 
         but seemingly no one else does. Come take a listen if you want to find hidden gems!<br>
         <br>
         The mix is made by calculating a score for each listen. This score is composed of two values:<br>
-        - The rank in {username}'s top 1000 recording of all time (First place get 100 points, second get 999.9, etc...)<br>
+        - The rank in {username}'s recordings of all time, scaled over all of them (First place get 100 points, last place get 0)<br>
         - The percentage of the recording's listens being from {username} (Made with this formula: (user listens / worldwide listens) *100)<br>
         <br>
         Made with: https://github.com/RustyNova016/Alistral"
@@ -89,8 +119,8 @@ pub async fn underrated_mix(
             .expect_fatal("Couldn't convert recordings for playlist"),
     };
 
-    target
-        .export(playlist, Some(username), Some(token))
+    exporter
+        .export(playlist, Some(username), token)
         .await
         .expect_fatal("Couldn't send the playlist");
 
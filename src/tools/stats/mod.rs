@@ -2,6 +2,7 @@ use crate::database::listenbrainz::listens::ListenFetchQuery;
 use crate::database::listenbrainz::listens::ListenFetchQueryReturn;
 use crate::models::cli::common::SortSorterBy;
 use crate::models::cli::common::StatsTarget;
+use crate::models::cli::common::Timeframe;
 
 mod artists;
 mod recordings;
@@ -14,11 +15,24 @@ pub async fn stats_command(
     username: &str,
     target: StatsTarget,
     _sort_by: SortSorterBy,
+    timeframe: Option<Timeframe>,
 ) {
-    let listens = ListenFetchQuery::builder()
+    let query = ListenFetchQuery::builder()
         //.fetch_recordings_redirects(true)
         .returns(ListenFetchQueryReturn::Mapped)
-        .user(username.to_string())
+        .user(username.to_string());
+
+    let query = match timeframe.and_then(|t| t.get_start_date()) {
+        Some(date) => query.min_listened_at(date),
+        None => query.min_listened_at_none(),
+    };
+
+    let query = match timeframe.and_then(|t| t.get_end_date()) {
+        Some(date) => query.max_listened_at(date),
+        None => query.max_listened_at_none(),
+    };
+
+    let listens = query
         .build()
         .fetch(conn)
         .await
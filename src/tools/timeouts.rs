@@ -0,0 +1,38 @@
+use chrono::Local;
+use musicbrainz_db_lite::models::musicbrainz::recording::Recording;
+
+use crate::api::clients::ALISTRAL_CLIENT;
+use crate::models::config::config_trait::ConfigFile as _;
+use crate::models::config::recording_timeout::RecordingTimeoutConfig;
+use crate::utils::cli::display::RecordingExt as _;
+
+/// Print the timed out recordings, with the end of their timeout
+pub async fn list_timeouts(conn: &mut sqlx::SqliteConnection) -> Result<(), crate::Error> {
+    // Don't keep the config locked while fetching the recordings
+    let timeouts = RecordingTimeoutConfig::load()?
+        .read_or_panic()
+        .get_timeouts()
+        .into_iter()
+        .map(|(mbid, deadline)| (mbid.clone(), *deadline))
+        .collect::<Vec<_>>();
+
+    if timeouts.is_empty() {
+        println!("No recordings are timed out");
+        return Ok(());
+    }
+
+    for (mbid, deadline) in timeouts {
+        let name =
+            match Recording::get_or_fetch(conn, &ALISTRAL_CLIENT.musicbrainz_db, &mbid).await? {
+                Some(recording) => recording.pretty_format_with_credits(conn, true).await?,
+                None => mbid,
+            };
+
+        println!(
+            "{name} - until {}",
+            deadline.with_timezone(&Local).format("%Y-%m-%d %H:%M")
+        );
+    }
+
+    Ok(())
+}
@@ -19,6 +19,17 @@ pub impl Relation<Recording, Artist> {
             && self.entity1 == base_recording.id
             && self.direction == "backward"
     }
+
+    /// Get the artist of the relationship
+    async fn get_artist(
+        &self,
+        conn: &mut sqlx::SqliteConnection,
+    ) -> Result<Option<Artist>, crate::Error> {
+        Ok(sqlx::query_as("SELECT * FROM artists WHERE id = ?")
+            .bind(self.entity0)
+            .fetch_optional(conn)
+            .await?)
+    }
 }
 
 #[ext]
@@ -1,5 +1,11 @@
+use std::sync::LazyLock;
+
 use regex::Regex;
 
+/// Matches the color codes and hyperlinks of a text formated for the terminal
+static TERMINAL_FORMATTING_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*[A-Za-z]|\x1b\]8;;[^\x1b]*\x1b\\").unwrap());
+
 pub fn is_string_mbid(string: &str) -> bool {
     let regex = Regex::new(
         r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$",
@@ -13,10 +19,17 @@ pub fn is_string_mbid(string: &str) -> bool {
 }
 
 pub fn get_raw_mbid_from_url(string: &str) -> Option<String> {
-    let regex = Regex::new(r"(recording|release|album|work|release-group|url)/([0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12})").unwrap();
+    let regex = Regex::new(r"(recording|release|album|work|release-group|url|playlist|artist|label|collection)/([0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12})").unwrap();
 
     // result will be a tuple containing the start and end indices for the first match in the string
     let caps = regex.captures(string)?;
 
     Some(caps.get(2)?.as_str().to_string())
 }
+
+/// Remove the colors and hyperlinks of a text formated for the terminal
+pub fn strip_terminal_formatting(string: &str) -> String {
+    TERMINAL_FORMATTING_REGEX
+        .replace_all(string, "")
+        .to_string()
+}